@@ -0,0 +1,143 @@
+//! Deterministic client order IDs
+//!
+//! Encodes the strategy, session, cycle, and sequence that produced an order
+//! into its client order ID, so fills can be attributed without bookkeeping.
+//! The session is derived from the process start time and pid, so cycle
+//! numbers that restart at 0 after a restart, or in a second process started
+//! the same second, don't reuse IDs.
+//!
+//! Format: `{tag}{session:6}{cycle:08x}{seq:04x}{hash:6}`, alphanumeric and
+//! at most 32 characters, which satisfies both Binance (36,
+//! `[.A-Z:/a-z0-9_-]`) and OKX (32, alphanumeric).
+
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Maximum client order ID length accepted by every supported venue
+pub const MAX_CLIENT_ORDER_ID_LEN: usize = 32;
+
+/// Width of the strategy hash suffix, in hex characters
+const HASH_LEN: usize = 6;
+
+/// Width of the session field, in base-36 characters
+const SESSION_LEN: usize = 6;
+
+/// Number of distinct sessions (36^6)
+const SESSION_MODULUS: u64 = 36u64.pow(SESSION_LEN as u32);
+
+/// Width of the fixed numeric fields (session + cycle + sequence)
+const FIELDS_LEN: usize = SESSION_LEN + 8 + 4;
+
+/// Maximum length of the strategy tag prefix (what's left of the ID)
+pub const MAX_TAG_LEN: usize = MAX_CLIENT_ORDER_ID_LEN - FIELDS_LEN - HASH_LEN;
+
+/// Session of a process started at `started` (unix seconds) with id `pid`
+fn session(started: u64, pid: u32) -> u64 {
+    let digest = Sha256::new()
+        .chain_update(started.to_be_bytes())
+        .chain_update(pid.to_be_bytes())
+        .finalize();
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+/// Fixed-width lowercase base-36 encoding of a session number
+fn encode_session(session: u64) -> String {
+    let mut session = session % SESSION_MODULUS;
+    let mut digits = [b'0'; SESSION_LEN];
+    for digit in digits.iter_mut().rev() {
+        *digit = b"0123456789abcdefghijklmnopqrstuvwxyz"[(session % 36) as usize];
+        session /= 36;
+    }
+    String::from_utf8_lossy(&digits).into_owned()
+}
+
+/// Generates client order IDs for one strategy run
+pub struct ClientOrderIdGenerator {
+    tag: String,
+    session: String,
+    strategy_hash: String,
+}
+
+impl ClientOrderIdGenerator {
+    /// Create a generator for a strategy, given a tag and the strategy graph
+    /// bytes; the session comes from the current time and process id
+    pub fn new(strategy_tag: &str, strategy_graph: &[u8]) -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self::for_session(strategy_tag, strategy_graph, session(started, std::process::id()))
+    }
+
+    /// Create a generator with a fixed session
+    fn for_session(strategy_tag: &str, strategy_graph: &[u8], session: u64) -> Self {
+        let tag: String = strategy_tag
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .take(MAX_TAG_LEN)
+            .collect();
+        let strategy_hash = hex::encode(Sha256::digest(strategy_graph))[..HASH_LEN].to_string();
+
+        Self {
+            tag,
+            session: encode_session(session),
+            strategy_hash,
+        }
+    }
+
+    /// Client order ID for the `seq`-th order of execution cycle `cycle`
+    pub fn generate(&self, cycle: u32, seq: u16) -> String {
+        format!(
+            "{}{}{:08x}{:04x}{}",
+            self.tag, self.session, cycle, seq, self.strategy_hash
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_layout() {
+        let generator = ClientOrderIdGenerator::for_session("market_making", b"graph bytes", 1_790_000_000);
+        let id = generator.generate(42, 3);
+
+        assert_eq!(id.len(), MAX_CLIENT_ORDER_ID_LEN);
+        assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert!(id.starts_with("marketma"));
+        assert_eq!(&id[MAX_TAG_LEN..MAX_TAG_LEN + SESSION_LEN], encode_session(1_790_000_000));
+        assert_eq!(&id[MAX_TAG_LEN + SESSION_LEN..MAX_TAG_LEN + FIELDS_LEN], "0000002a0003");
+
+        // Short tags give shorter IDs, never longer ones
+        let short = ClientOrderIdGenerator::new("m-m", b"graph bytes").generate(0, 0);
+        assert_eq!(short.len(), MAX_CLIENT_ORDER_ID_LEN - MAX_TAG_LEN + 2);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        let a = ClientOrderIdGenerator::for_session("grid", b"graph", 100);
+        let b = ClientOrderIdGenerator::for_session("grid", b"graph", 100);
+        let other = ClientOrderIdGenerator::for_session("grid", b"other graph", 100);
+
+        assert_eq!(a.generate(7, 0), b.generate(7, 0));
+        assert_ne!(a.generate(7, 0), a.generate(7, 1));
+        assert_ne!(a.generate(7, 0), other.generate(7, 0));
+
+        // A restarted process reuses cycle numbers but not IDs
+        let restarted = ClientOrderIdGenerator::for_session("grid", b"graph", 101);
+        assert_ne!(a.generate(0, 0), restarted.generate(0, 0));
+    }
+
+    #[test]
+    fn test_session_mixes_start_time_and_pid() {
+        // Two processes started in the same second get different sessions
+        let sessions = [session(1_790_000_000, 100), session(1_790_000_000, 101), session(1_790_000_001, 100)];
+        let encoded: Vec<String> = sessions.iter().map(|s| encode_session(*s)).collect();
+        assert_ne!(encoded[0], encoded[1]);
+        assert_ne!(encoded[0], encoded[2]);
+        assert_eq!(session(1_790_000_000, 100), sessions[0]);
+    }
+}
@@ -0,0 +1,533 @@
+//! Graph inspection
+//!
+//! Parses the human-readable debug representation of .0 files (the
+//! commented `Graph { ... }` blocks) into nodes and computes the strategy
+//! hash of the file. `interpreter` evaluates the parsed graphs.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use zerolang::Tensor;
+
+/// Kind of a graph node
+///
+/// Mirrors the node kinds of 0-lang's graph format. This tree has no
+/// composer to share them with, so they're defined here for the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeType {
+    Constant,
+    Operation,
+    External,
+    Branch,
+}
+
+impl NodeType {
+    /// Every node type, in display order
+    pub const ALL: [NodeType; 4] = [
+        NodeType::Constant,
+        NodeType::Operation,
+        NodeType::External,
+        NodeType::Branch,
+    ];
+}
+
+impl FromStr for NodeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Constant" => Ok(NodeType::Constant),
+            "Operation" => Ok(NodeType::Operation),
+            "External" => Ok(NodeType::External),
+            "Branch" => Ok(NodeType::Branch),
+            other => Err(format!("Unknown node type: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for NodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            NodeType::Constant => "Constant",
+            NodeType::Operation => "Operation",
+            NodeType::External => "External",
+            NodeType::Branch => "Branch",
+        };
+        f.pad(name)
+    }
+}
+
+/// One node of a graph
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    /// Node name (the argument of `sha256(...)`)
+    pub id: String,
+    /// Node type
+    pub node_type: NodeType,
+    /// Nodes this node reads from (for Branch: condition, true branch,
+    /// false branch, in that order)
+    pub inputs: Vec<String>,
+    /// Value of a Constant node
+    pub value: Option<Tensor>,
+    /// Operation of an Operation node (e.g. "Mul")
+    pub op: Option<String>,
+    /// URI of an External node
+    pub uri: Option<String>,
+    /// Threshold of a Branch node
+    pub threshold: Option<f32>,
+}
+
+/// Summary of one `Graph { ... }` block
+#[derive(Debug, Clone)]
+pub struct GraphSummary {
+    /// Declared graph name
+    pub name: Option<String>,
+    /// Nodes in declaration order
+    pub nodes: Vec<GraphNode>,
+    /// Declared entry point
+    pub entry_point: Option<String>,
+    /// Declared output nodes
+    pub outputs: Vec<String>,
+}
+
+impl GraphSummary {
+    /// Number of nodes of a given type
+    pub fn count(&self, node_type: NodeType) -> usize {
+        self.nodes.iter().filter(|n| n.node_type == node_type).count()
+    }
+
+    /// Nodes that feed data into the graph: External nodes without inputs
+    /// (market data) and `input_*` nodes supplied at runtime
+    pub fn input_ports(&self) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .filter(|n| {
+                (n.node_type == NodeType::External && n.inputs.is_empty())
+                    || n.id.starts_with("input_")
+            })
+            .map(|n| n.id.as_str())
+            .collect()
+    }
+
+    /// Look up a node by id
+    pub fn node(&self, id: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Replace the value of a scalar Constant node (e.g. a `config_*` parameter)
+    pub fn set_constant(&mut self, id: &str, value: f32) -> Result<(), String> {
+        let node = self
+            .nodes
+            .iter_mut()
+            .find(|n| n.id == id && n.node_type == NodeType::Constant)
+            .ok_or_else(|| format!("No constant '{}' in graph", id))?;
+
+        match &mut node.value {
+            Some(tensor) if tensor.data.len() == 1 => {
+                tensor.data[0] = value;
+                Ok(())
+            }
+            _ => Err(format!("Constant '{}' is not a scalar", id)),
+        }
+    }
+
+    /// Check that every referenced node is defined exactly once
+    fn validate(&self) -> Result<(), String> {
+        let graph = self.name.as_deref().unwrap_or("<unnamed>");
+        let mut ids = HashSet::new();
+        for node in &self.nodes {
+            if !ids.insert(node.id.as_str()) {
+                return Err(format!("Duplicate node '{}' in graph {}", node.id, graph));
+            }
+        }
+
+        let references = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.inputs.iter())
+            .chain(self.outputs.iter())
+            .chain(self.entry_point.iter());
+        for reference in references {
+            if !ids.contains(reference.as_str()) {
+                return Err(format!(
+                    "Reference to undefined node '{}' in graph {}",
+                    reference, graph
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A parsed .0 file
+#[derive(Debug, Clone)]
+pub struct GraphFile {
+    /// Path the file was loaded from
+    pub path: PathBuf,
+    /// SHA-256 of the file contents, hex-encoded
+    pub strategy_hash: String,
+    /// Graphs defined in the file
+    pub graphs: Vec<GraphSummary>,
+}
+
+impl GraphFile {
+    /// Load and parse a .0 file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes =
+            fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let source = std::str::from_utf8(&bytes)
+            .map_err(|_| format!("{} is not a text graph representation", path.display()))?;
+
+        let graphs = parse(source).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            strategy_hash: strategy_hash(&bytes),
+            graphs,
+        })
+    }
+}
+
+/// Strategy hash of a graph: SHA-256 of its bytes, hex-encoded
+pub fn strategy_hash(graph: &[u8]) -> String {
+    hex::encode(Sha256::digest(graph))
+}
+
+/// Node being read from a `{ ... }` block
+#[derive(Default)]
+struct PartialNode {
+    id: Option<String>,
+    node_type: Option<String>,
+    inputs: Vec<String>,
+    value: Option<String>,
+    op: Option<String>,
+    uri: Option<String>,
+    threshold: Option<String>,
+    condition: Option<String>,
+    true_branch: Option<String>,
+    false_branch: Option<String>,
+    start_line: usize,
+}
+
+impl PartialNode {
+    fn finish(self) -> Result<GraphNode, String> {
+        let line = self.start_line;
+        let id = self
+            .id
+            .ok_or_else(|| format!("Node at line {} has no id", line))?;
+        let node_type = self
+            .node_type
+            .ok_or_else(|| format!("Node '{}' at line {} has no type", id, line))?
+            .parse()
+            .map_err(|e| format!("{} (node '{}' at line {})", e, id, line))?;
+
+        let value = self
+            .value
+            .map(|text| parse_tensor(&text))
+            .transpose()
+            .map_err(|e| format!("{} (node '{}' at line {})", e, id, line))?;
+        let threshold = self
+            .threshold
+            .map(|t| t.parse::<f32>())
+            .transpose()
+            .map_err(|_| format!("Invalid threshold (node '{}' at line {})", id, line))?;
+
+        let mut inputs = self.inputs;
+        inputs.extend(self.condition);
+        inputs.extend(self.true_branch);
+        inputs.extend(self.false_branch);
+
+        Ok(GraphNode {
+            id,
+            node_type,
+            inputs,
+            value,
+            op: self.op,
+            uri: self.uri,
+            threshold,
+        })
+    }
+}
+
+/// Parse every `Graph { ... }` block in a graph's debug representation
+pub fn parse(source: &str) -> Result<Vec<GraphSummary>, String> {
+    let mut graphs = Vec::new();
+    let mut graph: Option<GraphSummary> = None;
+    let mut node: Option<PartialNode> = None;
+    let mut in_nodes = false;
+    let mut depth: i32 = 0;
+
+    for (index, raw) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let line = strip_comment(raw.trim().strip_prefix('#').unwrap_or(raw));
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some(current) = graph.as_mut() else {
+            if line == "Graph {" {
+                graph = Some(GraphSummary {
+                    name: None,
+                    nodes: Vec::new(),
+                    entry_point: None,
+                    outputs: Vec::new(),
+                });
+                depth = 1;
+            }
+            continue;
+        };
+
+        let before = depth;
+        depth += nesting_delta(line);
+        let field = line.split_once(':').map(|(k, v)| (k.trim(), v.trim()));
+
+        match (before, field) {
+            (1, Some(("name", value))) => current.name = parse_string(value),
+            (1, Some(("entry_point", value))) => current.entry_point = parse_string(value),
+            (1, Some(("outputs", value))) => current.outputs = parse_list(value),
+            (1, Some(("nodes", _))) => in_nodes = true,
+            (2, _) if in_nodes && line.starts_with('{') => {
+                node = Some(PartialNode {
+                    start_line: line_number,
+                    ..Default::default()
+                });
+            }
+            (3, Some((key, value))) if node.is_some() => {
+                let partial = node.as_mut().expect("checked above");
+                match key {
+                    "id" => partial.id = parse_string(value),
+                    "type" => partial.node_type = parse_string(value),
+                    "inputs" => partial.inputs.extend(parse_list(value)),
+                    "value" => partial.value = Some(value.to_string()),
+                    "op" => partial.op = parse_string(value),
+                    "uri" => partial.uri = parse_string(value),
+                    "threshold" => partial.threshold = parse_string(value),
+                    "condition" => partial.condition = parse_string(value),
+                    "true_branch" => partial.true_branch = parse_string(value),
+                    "false_branch" => partial.false_branch = parse_string(value),
+                    _ => {}
+                }
+            }
+            // Continuation of a multi-line `value: Tensor { ... }`
+            (b, _) if b > 3 => {
+                if let Some(value) = node.as_mut().and_then(|n| n.value.as_mut()) {
+                    value.push(' ');
+                    value.push_str(line);
+                }
+            }
+            _ => {}
+        }
+
+        if depth < 0 {
+            return Err(format!("Unbalanced brackets at line {}", line_number));
+        }
+        if depth <= 2 {
+            if let Some(partial) = node.take() {
+                current.nodes.push(partial.finish()?);
+            }
+        }
+        if depth <= 1 {
+            in_nodes = false;
+        }
+        if depth == 0 {
+            let finished = graph.take().expect("graph is open");
+            finished.validate()?;
+            graphs.push(finished);
+        }
+    }
+
+    if graph.is_some() {
+        return Err("Unterminated Graph block".to_string());
+    }
+    if graphs.is_empty() {
+        return Err("No Graph definition found".to_string());
+    }
+
+    Ok(graphs)
+}
+
+/// Drop a trailing `# comment`, ignoring `#` inside string literals
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return line[..i].trim(),
+            _ => {}
+        }
+    }
+    line.trim()
+}
+
+/// Net change in `{`/`[` nesting on a line, ignoring string literals
+fn nesting_delta(line: &str) -> i32 {
+    let mut in_string = false;
+    let mut delta = 0;
+    for c in line.chars() {
+        match c {
+            '"' => in_string = !in_string,
+            '{' | '[' if !in_string => delta += 1,
+            '}' | ']' if !in_string => delta -= 1,
+            _ => {}
+        }
+    }
+    delta
+}
+
+/// Read `"value"`, `sha256("value")` or a bare word
+fn parse_string(value: &str) -> Option<String> {
+    let value = value.trim().trim_end_matches(',').trim();
+    let value = value
+        .strip_prefix("sha256(")
+        .and_then(|v| v.strip_suffix(')'))
+        .unwrap_or(value);
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .unwrap_or(value);
+
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Read a `Tensor { shape: [..], data: [..], confidence: x }` literal
+fn parse_tensor(text: &str) -> Result<Tensor, String> {
+    let field = |name: &str| {
+        let start = text.find(&format!("{}:", name))? + name.len() + 1;
+        let rest = text[start..].trim_start();
+        let end = if rest.starts_with('[') {
+            rest.find(']')? + 1
+        } else {
+            rest.find([',', '}']).unwrap_or(rest.len())
+        };
+        Some(rest[..end].trim())
+    };
+    let numbers = |list: &str| -> Result<Vec<f32>, String> {
+        list.trim_start_matches('[')
+            .trim_end_matches(']')
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse().map_err(|_| format!("Invalid number in tensor: {}", v)))
+            .collect()
+    };
+
+    let data = numbers(field("data").ok_or("Tensor has no data")?)?;
+    let shape = match field("shape") {
+        Some(shape) => numbers(shape)?.into_iter().map(|d| d as u32).collect(),
+        None => vec![data.len() as u32],
+    };
+    let confidence = match field("confidence") {
+        Some(c) => c.parse().map_err(|_| format!("Invalid confidence: {}", c))?,
+        None => 1.0,
+    };
+
+    Ok(Tensor::new(shape, data, confidence))
+}
+
+/// Read a `["a", "b"]` list
+fn parse_list(value: &str) -> Vec<String> {
+    let value = value.trim().trim_end_matches(',').trim();
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .unwrap_or(value);
+
+    inner.split(',').filter_map(parse_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::STRATEGY_DIR;
+
+    #[test]
+    fn test_parse_market_making() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(STRATEGY_DIR)
+            .join("market_making.0");
+        let file = GraphFile::load(&path).unwrap();
+
+        assert_eq!(file.graphs.len(), 1);
+        assert_eq!(file.strategy_hash, strategy_hash(&fs::read(&path).unwrap()));
+
+        let graph = &file.graphs[0];
+        assert_eq!(graph.name.as_deref(), Some("market_making_v1"));
+        assert_eq!(graph.count(NodeType::Constant), 8);
+        assert_eq!(graph.count(NodeType::Operation), 5);
+        assert_eq!(graph.count(NodeType::External), 5);
+        assert_eq!(graph.count(NodeType::Branch), 2);
+        assert_eq!(graph.entry_point.as_deref(), Some("get_ticker"));
+        assert_eq!(graph.outputs, vec!["output"]);
+        assert_eq!(
+            graph.input_ports(),
+            vec!["get_ticker", "get_orderbook", "get_position"]
+        );
+    }
+
+    #[test]
+    fn test_parse_all_bundled_graphs() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for dir in ["graphs/strategies", "graphs/connectors", "examples"] {
+            for entry in fs::read_dir(root.join(dir)).unwrap() {
+                let path = entry.unwrap().path();
+                let file = GraphFile::load(&path).unwrap();
+                assert!(file.graphs.iter().all(|g| !g.nodes.is_empty()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_node_details() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(STRATEGY_DIR)
+            .join("grid_trading.0");
+        let graph = &GraphFile::load(&path).unwrap().graphs[0];
+
+        // Multi-line tensor literal with an embedded comment
+        let levels = graph.node("grid_levels").unwrap().value.as_ref().unwrap();
+        assert_eq!(levels.shape, vec![10]);
+        assert_eq!(levels.data[0], 0.95);
+        assert_eq!(levels.data[9], 1.05);
+
+        let prices = graph.node("grid_prices").unwrap();
+        assert_eq!(prices.op.as_deref(), Some("Mul"));
+        let orders = graph.node("place_grid_orders").unwrap();
+        assert_eq!(orders.uri.as_deref(), Some("binance:batch_order:symbol=BTCUSDT"));
+    }
+
+    #[test]
+    fn test_set_constant() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(STRATEGY_DIR)
+            .join("grid_trading.0");
+        let mut graph = GraphFile::load(&path).unwrap().graphs.remove(0);
+
+        graph.set_constant("config_order_size", 0.5).unwrap();
+        let size = graph.node("config_order_size").unwrap().value.as_ref().unwrap();
+        assert_eq!(size.data, vec![0.5]);
+
+        assert!(graph.set_constant("grid_levels", 1.0).is_err());
+        assert!(graph.set_constant("grid_prices", 1.0).is_err());
+        assert!(graph.set_constant("config_missing", 1.0).is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_graphs() {
+        assert!(parse("# nothing here").is_err());
+        assert!(parse("# Graph {\n#     nodes: [\n").is_err());
+
+        let unknown_type = "# Graph {\n# nodes: [\n# {\n# id: sha256(\"a\"),\n# type: Loop\n# }\n# ]\n# }";
+        assert!(parse(unknown_type).unwrap_err().contains("Unknown node type"));
+
+        let dangling = "# Graph {\n# nodes: [\n# {\n# id: sha256(\"a\"),\n# type: Constant\n# }\n# ],\n# outputs: [\"b\"]\n# }";
+        assert!(parse(dangling).unwrap_err().contains("undefined node 'b'"));
+    }
+}
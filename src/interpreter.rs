@@ -0,0 +1,374 @@
+//! Graph interpreter
+//!
+//! The bundled .0 files hold the debug representation of their graphs (the
+//! commented `Graph { ... }` blocks), not a serialized graph, so the 0-VM
+//! can't load them. Until they ship in a form the VM loads, the runtime
+//! evaluates the graphs `graph` parses here. Only the node kinds and
+//! operations the bundled graphs use are supported (`SUPPORTED_OPS`).
+
+use std::collections::{HashMap, HashSet};
+use zerolang::{ExternalResolver, Tensor};
+
+use crate::graph::{GraphNode, GraphSummary, NodeType};
+
+/// Branch threshold when a Branch node doesn't declare one
+const DEFAULT_BRANCH_THRESHOLD: f32 = 0.5;
+
+/// Operations `apply_op` implements
+pub const SUPPORTED_OPS: &[&str] = &[
+    "Add", "Sub", "Mul", "Div", "Gt", "Lt", "Concat", "Identity", "JsonGet",
+];
+
+/// Check that every Operation in a graph is one the interpreter supports
+pub fn check_ops(graph: &GraphSummary) -> Result<(), String> {
+    for node in graph.nodes.iter().filter(|n| n.node_type == NodeType::Operation) {
+        let op = node.op.as_deref().unwrap_or_default();
+        if !SUPPORTED_OPS.contains(&op) {
+            return Err(format!(
+                "Operation '{}' uses unsupported op {} (supported: {})",
+                node.id,
+                op,
+                SUPPORTED_OPS.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Evaluate a graph's outputs, resolving External nodes through `resolver`
+///
+/// Evaluation is lazy from the outputs: a node runs only if an output
+/// depends on it, and a Branch evaluates only the side its condition
+/// selects, so the untaken side's External calls (orders) never happen.
+/// Each node runs at most once per evaluation.
+pub fn evaluate(graph: &GraphSummary, resolver: &dyn ExternalResolver) -> Result<Vec<Tensor>, String> {
+    if graph.outputs.is_empty() {
+        return Err("Graph declares no outputs".to_string());
+    }
+
+    let mut evaluator = Evaluator {
+        graph,
+        resolver,
+        values: HashMap::new(),
+        in_progress: HashSet::new(),
+    };
+    graph.outputs.iter().map(|id| evaluator.eval(id)).collect()
+}
+
+/// Lazy, memoized evaluation of one graph
+struct Evaluator<'a> {
+    graph: &'a GraphSummary,
+    resolver: &'a dyn ExternalResolver,
+    values: HashMap<&'a str, Tensor>,
+    in_progress: HashSet<&'a str>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn eval(&mut self, id: &str) -> Result<Tensor, String> {
+        let node = self
+            .graph
+            .node(id)
+            .ok_or_else(|| format!("Reference to undefined node '{}'", id))?;
+        let id = node.id.as_str();
+
+        if let Some(value) = self.values.get(id) {
+            return Ok(value.clone());
+        }
+        if !self.in_progress.insert(id) {
+            return Err(format!("Cycle through node '{}'", id));
+        }
+
+        let value = self.eval_node(node);
+        self.in_progress.remove(id);
+        let value = value?;
+        self.values.insert(id, value.clone());
+        Ok(value)
+    }
+
+    fn eval_node(&mut self, node: &'a GraphNode) -> Result<Tensor, String> {
+        match node.node_type {
+            NodeType::Constant => node
+                .value
+                .clone()
+                .ok_or_else(|| format!("Constant '{}' has no value", node.id)),
+            NodeType::External => {
+                let uri = node
+                    .uri
+                    .as_deref()
+                    .ok_or_else(|| format!("External '{}' has no uri", node.id))?;
+                let inputs = self.eval_inputs(&node.inputs)?;
+                self.resolver
+                    .resolve(uri, inputs.iter().collect())
+                    .map_err(|e| format!("{}: {}", node.id, e))
+            }
+            NodeType::Operation => {
+                let op = node
+                    .op
+                    .as_deref()
+                    .ok_or_else(|| format!("Operation '{}' has no op", node.id))?;
+                let inputs = self.eval_inputs(&node.inputs)?;
+                apply_op(op, &inputs).map_err(|e| format!("{}: {}", node.id, e))
+            }
+            NodeType::Branch => {
+                let [condition, true_branch, false_branch] = node.inputs.as_slice() else {
+                    return Err(format!(
+                        "Branch '{}' needs a condition and two branches",
+                        node.id
+                    ));
+                };
+                let condition = self.eval(condition)?;
+                let threshold = node.threshold.unwrap_or(DEFAULT_BRANCH_THRESHOLD);
+                let taken = match condition.data.first() {
+                    Some(value) if *value > threshold => true_branch,
+                    _ => false_branch,
+                };
+                self.eval(taken)
+            }
+        }
+    }
+
+    fn eval_inputs(&mut self, ids: &[String]) -> Result<Vec<Tensor>, String> {
+        ids.iter().map(|id| self.eval(id)).collect()
+    }
+}
+
+/// Apply an Operation to its evaluated inputs
+///
+/// Arithmetic and comparisons are elementwise with scalar broadcasting;
+/// comparisons yield 1.0 or 0.0. The result carries the lowest input
+/// confidence. JsonGet passes its input through, since resolvers already
+/// return parsed tensors.
+fn apply_op(op: &str, inputs: &[Tensor]) -> Result<Tensor, String> {
+    let confidence = inputs.iter().map(|t| t.confidence).fold(1.0, f32::min);
+
+    let binary = |f: fn(f32, f32) -> f32| -> Result<Tensor, String> {
+        let [a, b] = inputs else {
+            return Err(format!("{} expects 2 inputs, got {}", op, inputs.len()));
+        };
+        let (len, shape) = match (a.data.len(), b.data.len()) {
+            (x, y) if x == y => (x, a.shape.clone()),
+            (1, y) => (y, b.shape.clone()),
+            (x, 1) => (x, a.shape.clone()),
+            (x, y) => return Err(format!("{} shape mismatch: {} vs {} elements", op, x, y)),
+        };
+        let at = |t: &Tensor, i: usize| t.data[if t.data.len() == 1 { 0 } else { i }];
+        let data = (0..len).map(|i| f(at(a, i), at(b, i))).collect();
+        Ok(Tensor::new(shape, data, confidence))
+    };
+
+    match op {
+        "Add" => binary(|a, b| a + b),
+        "Sub" => binary(|a, b| a - b),
+        "Mul" => binary(|a, b| a * b),
+        "Div" => {
+            if inputs.get(1).is_some_and(|d| d.data.contains(&0.0)) {
+                return Err("Div by zero".to_string());
+            }
+            binary(|a, b| a / b)
+        }
+        "Gt" => binary(|a, b| if a > b { 1.0 } else { 0.0 }),
+        "Lt" => binary(|a, b| if a < b { 1.0 } else { 0.0 }),
+        "Concat" => {
+            let data: Vec<f32> = inputs.iter().flat_map(|t| t.data.iter().copied()).collect();
+            Ok(Tensor::new(vec![data.len() as u32], data, confidence))
+        }
+        "Identity" | "JsonGet" => match inputs {
+            [input] => Ok(Tensor::new(input.shape.clone(), input.data.clone(), confidence)),
+            _ => Err(format!("{} expects 1 input, got {}", op, inputs.len())),
+        },
+        _ => Err(format!("Unsupported operation: {}", op)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{parse, GraphFile};
+    use std::fs;
+    use std::path::Path;
+
+    /// Resolver returning a fixed ticker and recording every URI it sees
+    struct Recorder(std::sync::Mutex<Vec<String>>);
+
+    impl ExternalResolver for Recorder {
+        fn resolve(&self, uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+            self.0.lock().unwrap().push(uri.to_string());
+            match inputs.first() {
+                Some(price) => Ok(Tensor::new(vec![1], vec![price.data[0]], 1.0)),
+                None => Ok(Tensor::scalar(100.0, 0.9)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_takes_one_branch() {
+        let source = r#"
+# Graph {
+#     nodes: [
+#         {
+#             id: sha256("price"),
+#             type: External,
+#             uri: "test:ticker"
+#         },
+#         {
+#             id: sha256("limit"),
+#             type: Constant,
+#             value: Tensor { shape: [1], data: [50.0], confidence: 1.0 }
+#         },
+#         {
+#             id: sha256("half"),
+#             type: Constant,
+#             value: Tensor { shape: [1], data: [0.5], confidence: 1.0 }
+#         },
+#         {
+#             id: sha256("bid"),
+#             type: Operation,
+#             op: Mul,
+#             inputs: ["price", "half"]
+#         },
+#         {
+#             id: sha256("high"),
+#             type: Operation,
+#             op: Gt,
+#             inputs: ["price", "limit"]
+#         },
+#         {
+#             id: sha256("buy"),
+#             type: External,
+#             uri: "test:buy",
+#             inputs: ["bid"]
+#         },
+#         {
+#             id: sha256("sell"),
+#             type: External,
+#             uri: "test:sell",
+#             inputs: ["bid"]
+#         },
+#         {
+#             id: sha256("decide"),
+#             type: Branch,
+#             condition: "high",
+#             threshold: 0.5,
+#             true_branch: "sell",
+#             false_branch: "buy"
+#         }
+#     ],
+#     outputs: ["decide", "bid"]
+# }"#;
+        let graph = &parse(source).unwrap()[0];
+        let resolver = Recorder(std::sync::Mutex::new(Vec::new()));
+
+        let outputs = evaluate(graph, &resolver).unwrap();
+        assert_eq!(outputs[0].data, vec![50.0]);
+        assert_eq!(outputs[1].data, vec![50.0]);
+        // Lowest input confidence carries through
+        assert_eq!(outputs[1].confidence, 0.9);
+
+        // The ticker is fetched once and only the taken branch is resolved
+        assert_eq!(*resolver.0.lock().unwrap(), vec!["test:ticker", "test:sell"]);
+    }
+
+    #[test]
+    fn test_apply_op() {
+        let scalar = Tensor::new(vec![1], vec![2.0], 1.0);
+        let vector = Tensor::new(vec![3], vec![1.0, 2.0, 3.0], 0.5);
+
+        let product = apply_op("Mul", &[scalar.clone(), vector.clone()]).unwrap();
+        assert_eq!(product.data, vec![2.0, 4.0, 6.0]);
+        assert_eq!(product.shape, vec![3]);
+        assert_eq!(product.confidence, 0.5);
+
+        let lt = apply_op("Lt", &[vector.clone(), scalar.clone()]).unwrap();
+        assert_eq!(lt.data, vec![1.0, 0.0, 0.0]);
+
+        let joined = apply_op("Concat", &[scalar.clone(), vector.clone()]).unwrap();
+        assert_eq!(joined.data.len(), 4);
+
+        let zero = Tensor::new(vec![1], vec![0.0], 1.0);
+        assert!(apply_op("Div", &[scalar.clone(), zero]).is_err());
+        assert!(apply_op("Add", &[vector.clone(), Tensor::new(vec![2], vec![1.0, 1.0], 1.0)]).is_err());
+        assert!(apply_op("Pow", &[scalar]).is_err());
+    }
+
+    /// Resolver quoting a mid of 100 and recording the inputs of every call
+    struct Quotes(std::sync::Mutex<Vec<Vec<f32>>>);
+
+    impl ExternalResolver for Quotes {
+        fn resolve(&self, _uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+            let values = inputs.iter().flat_map(|t| t.data.iter().copied()).collect();
+            self.0.lock().unwrap().push(values);
+            Ok(Tensor::scalar(100.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn test_simple_market_maker_quotes_half_spread() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/simple_market_maker.0");
+        let graph = &GraphFile::load(&path).unwrap().graphs[0];
+        let resolver = Quotes(std::sync::Mutex::new(Vec::new()));
+
+        evaluate(graph, &resolver).unwrap();
+        // A 1% spread puts the quotes 0.5% either side of mid
+        let calls = resolver.0.lock().unwrap();
+        let [bid, ask] = calls.last().unwrap()[..] else {
+            panic!("expected a bid and an ask");
+        };
+        assert!((bid - 99.5).abs() < 1e-4 && (ask - 100.5).abs() < 1e-4, "{} {}", bid, ask);
+    }
+
+    #[test]
+    fn test_bundled_graph_ops_are_supported() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        for dir in ["graphs/strategies", "graphs/connectors", "examples"] {
+            for entry in fs::read_dir(root.join(dir)).unwrap() {
+                let path = entry.unwrap().path();
+                for graph in GraphFile::load(&path).unwrap().graphs {
+                    if let Err(e) = check_ops(&graph) {
+                        panic!("{}: {}", path.display(), e);
+                    }
+                }
+            }
+        }
+
+        let pow = "# Graph {\n# nodes: [\n# {\n# id: sha256(\"a\"),\n# type: Operation,\n# op: Pow\n# }\n# ]\n# }";
+        let err = check_ops(&parse(pow).unwrap()[0]).unwrap_err();
+        assert!(err.contains("unsupported op Pow"), "{}", err);
+    }
+
+    #[test]
+    fn test_op_semantics() {
+        let a = Tensor::new(vec![2], vec![6.0, 1.0], 0.8);
+        let b = Tensor::new(vec![2], vec![2.0, 4.0], 0.6);
+        let cases: [(&str, Vec<f32>); 6] = [
+            ("Add", vec![8.0, 5.0]),
+            ("Sub", vec![4.0, -3.0]),
+            ("Mul", vec![12.0, 4.0]),
+            ("Div", vec![3.0, 0.25]),
+            ("Gt", vec![1.0, 0.0]),
+            ("Lt", vec![0.0, 1.0]),
+        ];
+        for (op, expected) in cases {
+            let result = apply_op(op, &[a.clone(), b.clone()]).unwrap();
+            assert_eq!(result.data, expected, "{}", op);
+            assert_eq!(result.shape, vec![2], "{}", op);
+            assert_eq!(result.confidence, 0.6, "{}", op);
+            assert!(apply_op(op, std::slice::from_ref(&a)).is_err(), "{}", op);
+        }
+
+        let joined = apply_op("Concat", &[a.clone(), b.clone()]).unwrap();
+        assert_eq!(joined.data, vec![6.0, 1.0, 2.0, 4.0]);
+        assert_eq!(joined.shape, vec![4]);
+
+        for op in ["Identity", "JsonGet"] {
+            let passed = apply_op(op, std::slice::from_ref(&a)).unwrap();
+            assert_eq!((passed.shape, passed.data), (a.shape.clone(), a.data.clone()));
+            assert!(apply_op(op, &[a.clone(), b.clone()]).is_err());
+        }
+
+        // Every supported op is implemented
+        for op in SUPPORTED_OPS {
+            let err = apply_op(op, &[]).err().unwrap_or_default();
+            assert!(!err.contains("Unsupported"), "{}", op);
+        }
+    }
+}
@@ -3,18 +3,27 @@
 //! Trading strategies as executable graphs.
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
+mod client_id;
+mod graph;
+mod interpreter;
 mod resolvers;
+mod retry;
 mod runtime;
+mod strategies;
 
 /// 0-hummingbot: High-frequency crypto trading bot
 #[derive(Parser)]
 #[command(name = "0-hummingbot")]
 #[command(about = "Trading strategies as executable graphs", long_about = None)]
 struct Cli {
+    /// Output format (human or json)
+    #[arg(long, global = true, default_value = "human")]
+    format: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -34,14 +43,19 @@ enum Commands {
 
     /// Run a strategy continuously
     Run {
-        /// Path to the strategy .0 graph file
+        /// Built-in strategy name or path to a .0 graph file
         #[arg(value_name = "STRATEGY")]
-        strategy: PathBuf,
+        strategy: String,
 
         /// Exchange connector to use
         #[arg(short, long, default_value = "binance")]
         connector: String,
 
+        /// Build the connector from a JSON `ConnectorConfig` file instead of
+        /// --connector and the {VENUE}_API_KEY/{VENUE}_API_SECRET defaults
+        #[arg(long, value_name = "FILE", conflicts_with = "connector")]
+        connector_config: Option<PathBuf>,
+
         /// Trading pair (e.g., BTC/USDT)
         #[arg(short, long)]
         pair: String,
@@ -53,6 +67,26 @@ enum Commands {
         /// Execution interval in milliseconds
         #[arg(short, long, default_value = "1000")]
         interval: u64,
+
+        /// Strategy parameter override (repeatable), e.g. --param spread=0.001
+        #[arg(long = "param", value_name = "NAME=VALUE")]
+        params: Vec<String>,
+
+        /// Skip orders whose inputs carry less confidence than this
+        #[arg(long, default_value_t = runtime::DEFAULT_MIN_CONFIDENCE)]
+        min_confidence: f32,
+
+        /// Skip market orders whose estimated slippage exceeds this many basis points
+        #[arg(long, value_name = "BPS")]
+        max_slippage_bps: Option<f32>,
+
+        /// Record every External result the strategy sees to FILE, cycle by cycle
+        #[arg(long, value_name = "FILE")]
+        record: Option<PathBuf>,
+
+        /// Replay a recording made with --record instead of trading
+        #[arg(long, value_name = "FILE", conflicts_with = "record")]
+        replay: Option<PathBuf>,
     },
 
     /// Inspect a graph without executing
@@ -69,6 +103,13 @@ enum Commands {
         graph: PathBuf,
     },
 
+    /// Show a strategy's parameter schema
+    DescribeStrategy {
+        /// Built-in strategy name or path to a .0 graph file
+        #[arg(value_name = "STRATEGY")]
+        strategy: String,
+    },
+
     /// List available strategies
     ListStrategies,
 
@@ -95,14 +136,35 @@ impl std::str::FromStr for TradingMode {
     }
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(OutputFormat::Human),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("Unknown output format: {}", s)),
+        }
+    }
+}
+
 fn main() {
-    // Initialize logging
+    // Initialize logging (stderr, so --format json output stays parseable)
     let subscriber = FmtSubscriber::builder()
         .with_max_level(Level::INFO)
+        .with_writer(std::io::stderr)
         .finish();
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set subscriber");
 
     let cli = Cli::parse();
+    let format = cli.format;
 
     match cli.command {
         Commands::Execute { graph, verbose } => {
@@ -115,29 +177,69 @@ fn main() {
         Commands::Run {
             strategy,
             connector,
+            connector_config: config_file,
             pair,
             mode,
             interval,
+            params,
+            min_confidence,
+            max_slippage_bps,
+            record,
+            replay,
         } => {
+            let strategy = resolve_strategy(&strategy);
+            let params = match strategy.resolve_params(&params) {
+                Ok(params) => params,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            };
+            let paper_mode = matches!(mode, TradingMode::Paper);
+            let mut connector = match config_file {
+                Some(path) => load_connector_config(&path),
+                None => connector_config(&connector, paper_mode),
+            };
+            if max_slippage_bps.is_some() {
+                connector.max_slippage_bps = max_slippage_bps;
+            }
             info!(
                 "Running strategy: {:?} on {} with pair {} in {:?} mode",
-                strategy, connector, pair, mode
+                strategy.path, connector.venue, pair, mode
             );
-            run_strategy(&strategy, &connector, &pair, mode, interval);
+            info!("Strategy parameters: {:?}", params);
+
+            let config = runtime::RuntimeConfig {
+                strategy_path: strategy.path.to_string_lossy().into_owned(),
+                connector,
+                pair,
+                interval_ms: interval,
+                paper_mode,
+                min_confidence,
+                params,
+                record_path: record.map(|path| path.to_string_lossy().into_owned()),
+            };
+            match replay {
+                Some(path) => replay_strategy(config, &path),
+                None => run_strategy(config),
+            }
         }
         Commands::Inspect { graph } => {
             info!("Inspecting graph: {:?}", graph);
-            inspect_graph(&graph);
+            inspect_graph(&graph, format);
         }
         Commands::Verify { graph } => {
             info!("Verifying graph: {:?}", graph);
-            verify_graph(&graph);
+            verify_graph(&graph, format);
+        }
+        Commands::DescribeStrategy { strategy } => {
+            describe_strategy(&resolve_strategy(&strategy), format);
         }
         Commands::ListStrategies => {
-            list_strategies();
+            list_strategies(format);
         }
         Commands::ListConnectors => {
-            list_connectors();
+            list_connectors(format);
         }
     }
 }
@@ -156,67 +258,252 @@ fn execute_graph(path: &PathBuf, _verbose: bool) {
     println!("└─────────────────────────────────────────────────────────────┘");
 }
 
-fn run_strategy(
-    _path: &PathBuf,
-    _connector: &str,
-    _pair: &str,
-    _mode: TradingMode,
-    _interval: u64,
-) {
-    println!("┌─────────────────────────────────────────────────────────────┐");
-    println!("│  RUN STRATEGY                                               │");
-    println!("├─────────────────────────────────────────────────────────────┤");
-    println!("│  Status: Not yet implemented                                │");
-    println!("│                                                             │");
-    println!("│  Coming soon:                                               │");
-    println!("│  - Continuous execution loop                                │");
-    println!("│  - Real-time market data                                    │");
-    println!("│  - Order placement                                          │");
-    println!("└─────────────────────────────────────────────────────────────┘");
+/// Connector configuration for a run; live trading reads
+/// {CONNECTOR}_API_KEY / {CONNECTOR}_API_SECRET
+fn connector_config(connector: &str, paper_mode: bool) -> resolvers::ConnectorConfig {
+    let mut config = resolvers::ConnectorConfig::new(connector);
+    if !paper_mode {
+        let prefix = connector.to_uppercase();
+        config.api_key_env = Some(format!("{}_API_KEY", prefix));
+        config.api_secret_env = Some(format!("{}_API_SECRET", prefix));
+    }
+    config
+}
+
+fn load_connector_config(path: &Path) -> resolvers::ConnectorConfig {
+    let result = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        .and_then(|json| resolvers::ConnectorConfig::from_json(&json));
+
+    match result {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_strategy(config: runtime::RuntimeConfig) {
+    let result = runtime::TradingRuntime::new(config).and_then(|mut runtime| {
+        tokio::runtime::Runtime::new()
+            .map_err(|e| format!("Failed to start async runtime: {}", e))?
+            .block_on(runtime.run())
+    });
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn replay_strategy(config: runtime::RuntimeConfig, path: &Path) {
+    let result = runtime::TradingRuntime::new(config).and_then(|runtime| runtime.replay(path));
+
+    match result {
+        Ok(cycles) => println!("Replayed {} cycles from {}", cycles, path.display()),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn inspect_graph(path: &PathBuf) {
+fn inspect_graph(path: &Path, format: OutputFormat) {
+    let file = match graph::GraphFile::load(path) {
+        Ok(file) => file,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if format == OutputFormat::Json {
+        let graphs: Vec<_> = file
+            .graphs
+            .iter()
+            .map(|graph| {
+                let counts: serde_json::Map<_, _> = graph::NodeType::ALL
+                    .iter()
+                    .map(|t| (t.to_string(), graph.count(*t).into()))
+                    .collect();
+                serde_json::json!({
+                    "name": graph.name,
+                    "nodes": graph.nodes.len(),
+                    "node_counts": counts,
+                    "inputs": graph.input_ports(),
+                    "outputs": graph.outputs,
+                    "entry_point": graph.entry_point,
+                })
+            })
+            .collect();
+        let output = serde_json::json!({
+            "path": file.path,
+            "strategy_hash": file.strategy_hash,
+            "graphs": graphs,
+        });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+        return;
+    }
+
     println!("┌─────────────────────────────────────────────────────────────┐");
     println!("│  INSPECT GRAPH                                              │");
     println!("├─────────────────────────────────────────────────────────────┤");
-    println!("│  Path: {:?}", path);
-    println!("│  Status: Not yet implemented                                │");
+    println!("│  Path: {}", file.path.display());
+    println!("│  Strategy hash: {}", file.strategy_hash);
+    for graph in &file.graphs {
+        println!("│                                                             │");
+        println!("│  Graph: {}", graph.name.as_deref().unwrap_or("-"));
+        println!("│  Nodes: {}", graph.nodes.len());
+        for node_type in graph::NodeType::ALL {
+            println!("│    {:<10} {}", node_type, graph.count(node_type));
+        }
+        println!("│  Inputs: {}", join_or_dash(&graph.input_ports()));
+        println!("│  Outputs: {}", join_or_dash(&graph.outputs));
+        if let Some(entry_point) = &graph.entry_point {
+            println!("│  Entry point: {}", entry_point);
+        }
+    }
+    println!("│                                                             │");
     println!("└─────────────────────────────────────────────────────────────┘");
 }
 
-fn verify_graph(path: &PathBuf) {
+/// Comma-separated list, or "-" when empty
+fn join_or_dash<S: AsRef<str>>(items: &[S]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.iter().map(|s| s.as_ref()).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Verification isn't implemented yet, so this always exits non-zero: a
+/// script must never mistake "not checked" for "verified"
+fn verify_graph(path: &Path, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let output = serde_json::json!({
+            "path": path,
+            "status": "not_implemented",
+        });
+        println!("{}", serde_json::to_string_pretty(&output).unwrap_or_default());
+    } else {
+        println!("┌─────────────────────────────────────────────────────────────┐");
+        println!("│  VERIFY GRAPH                                               │");
+        println!("├─────────────────────────────────────────────────────────────┤");
+        println!("│  Path: {:?}", path);
+        println!("│  Status: Not yet implemented                                │");
+        println!("└─────────────────────────────────────────────────────────────┘");
+    }
+
+    eprintln!("Error: graph verification is not implemented yet");
+    std::process::exit(1);
+}
+
+/// Resolve a strategy name or path, exiting with an error if unknown
+fn resolve_strategy(name_or_path: &str) -> strategies::StrategyInfo {
+    match strategies::resolve(name_or_path) {
+        Ok(strategy) => strategy,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn describe_strategy(strategy: &strategies::StrategyInfo, format: OutputFormat) {
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(strategy).unwrap_or_default());
+        return;
+    }
+
     println!("┌─────────────────────────────────────────────────────────────┐");
-    println!("│  VERIFY GRAPH                                               │");
+    println!("│  STRATEGY: {}", strategy.name);
     println!("├─────────────────────────────────────────────────────────────┤");
-    println!("│  Path: {:?}", path);
-    println!("│  Status: Not yet implemented                                │");
+    println!("│  Graph: {}", strategy.graph_name.as_deref().unwrap_or("-"));
+    println!("│  Path: {}", strategy.path.display());
+    if let Some(description) = &strategy.description {
+        println!("│  Description: {}", description);
+    }
+    println!("│                                                             │");
+    println!("│  Parameter            │ Type    │ Default │ Range           │");
+    println!("│  ─────────────────────┼─────────┼─────────┼──────────────── │");
+    for param in &strategy.params {
+        let kind = match param.kind {
+            strategies::ParamKind::Number => "number",
+            strategies::ParamKind::Integer => "integer",
+        };
+        let max = param.max.map_or("∞".to_string(), |max| max.to_string());
+        println!(
+            "│  {:<20} │ {:<7} │ {:<7} │ {}..={}",
+            param.name, kind, param.default, param.min, max
+        );
+        println!("│    {}", param.description);
+    }
+    if strategy.params.is_empty() {
+        println!("│  (no tunable parameters)                                    │");
+    }
+    println!("│                                                             │");
     println!("└─────────────────────────────────────────────────────────────┘");
 }
 
-fn list_strategies() {
+fn list_strategies(format: OutputFormat) {
+    let registry = match strategies::StrategyRegistry::builtin() {
+        Ok(registry) => registry,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let strategies = registry.list();
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(strategies).unwrap_or_default());
+        return;
+    }
+
     println!("┌─────────────────────────────────────────────────────────────┐");
     println!("│  AVAILABLE STRATEGIES                                       │");
     println!("├─────────────────────────────────────────────────────────────┤");
     println!("│                                                             │");
-    println!("│  Strategy         │ Status      │ Path                      │");
-    println!("│  ─────────────────┼─────────────┼───────────────────────── │");
-    println!("│  market_making    │ In Progress │ graphs/strategies/       │");
-    println!("│  arbitrage        │ Planned     │ graphs/strategies/       │");
-    println!("│  grid_trading     │ Planned     │ graphs/strategies/       │");
+    println!("│  Strategy         │ Graph              │ Path               │");
+    println!("│  ─────────────────┼────────────────────┼─────────────────── │");
+    for strategy in strategies {
+        println!(
+            "│  {:<16} │ {:<18} │ {}",
+            strategy.name,
+            strategy.graph_name.as_deref().unwrap_or("-"),
+            strategy.path.display()
+        );
+    }
     println!("│                                                             │");
     println!("└─────────────────────────────────────────────────────────────┘");
 }
 
-fn list_connectors() {
+fn list_connectors(format: OutputFormat) {
+    let connectors = resolvers::registered_connectors();
+
+    if format == OutputFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&connectors).unwrap_or_default());
+        return;
+    }
+
     println!("┌─────────────────────────────────────────────────────────────┐");
     println!("│  AVAILABLE CONNECTORS                                       │");
     println!("├─────────────────────────────────────────────────────────────┤");
     println!("│                                                             │");
-    println!("│  Exchange     │ Type │ Status      │ Path                   │");
-    println!("│  ─────────────┼──────┼─────────────┼────────────────────── │");
-    println!("│  binance      │ CEX  │ In Progress │ graphs/connectors/    │");
-    println!("│  okx          │ CEX  │ Planned     │ graphs/connectors/    │");
-    println!("│  hyperliquid  │ DEX  │ Planned     │ graphs/connectors/    │");
+    println!("│  Exchange     │ Type │ Resolver │ Endpoints                 │");
+    println!("│  ─────────────┼──────┼──────────┼────────────────────────── │");
+    for connector in &connectors {
+        let endpoints = if connector.endpoints.is_empty() {
+            "raw HTTP only".to_string()
+        } else {
+            connector.endpoints.join(", ")
+        };
+        println!(
+            "│  {:<12} │ {:<4} │ {:<8} │ {}",
+            connector.name, connector.exchange_type, connector.resolver, endpoints
+        );
+    }
     println!("│                                                             │");
     println!("└─────────────────────────────────────────────────────────────┘");
 }
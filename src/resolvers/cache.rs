@@ -0,0 +1,307 @@
+//! Resolver result cache
+//!
+//! Records what each External node resolved to during live execution so a
+//! later replay can feed the exact same values instead of re-fetching.
+//! Recordings are JSON lines, one `CacheEntry` per call in call order, so
+//! they can be appended to while the strategy runs.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, Write};
+use std::sync::{Arc, Mutex};
+use zerolang::{ExternalResolver, Tensor};
+
+/// Serializable copy of a resolved tensor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedTensor {
+    pub shape: Vec<u32>,
+    pub data: Vec<f32>,
+    pub confidence: f32,
+}
+
+impl From<&Tensor> for CachedTensor {
+    fn from(tensor: &Tensor) -> Self {
+        Self {
+            shape: tensor.shape.clone(),
+            data: tensor.data.clone(),
+            confidence: tensor.confidence,
+        }
+    }
+}
+
+impl From<&CachedTensor> for Tensor {
+    fn from(cached: &CachedTensor) -> Self {
+        Tensor::new(cached.shape.clone(), cached.data.clone(), cached.confidence)
+    }
+}
+
+/// One recorded External call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    /// `input_hash` of the call
+    pub key: String,
+    /// URI the External node resolved
+    pub uri: String,
+    /// Result returned to the graph (errors are replayed too)
+    pub result: Result<CachedTensor, String>,
+}
+
+/// Recorded External results
+///
+/// Recording appends calls in call order until they're taken (e.g. to
+/// append them to a recording file). Replaying serves loaded calls by the
+/// hash of URI and inputs; repeated calls with the same hash are replayed
+/// in the order they were recorded.
+#[derive(Debug, Default)]
+pub struct ResolverCache {
+    recorded: Vec<CacheEntry>,
+    replay: HashMap<String, VecDeque<CacheEntry>>,
+}
+
+impl ResolverCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load a recording written by `write_jsonl` for replay
+    pub fn read_jsonl(reader: impl BufRead) -> Result<Self, String> {
+        let mut cache = Self::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| format!("Failed to read recording: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: CacheEntry = serde_json::from_str(&line)
+                .map_err(|e| format!("Invalid recording line {}: {}", number + 1, e))?;
+            cache.replay.entry(entry.key.clone()).or_default().push_back(entry);
+        }
+        Ok(cache)
+    }
+
+    /// Append entries to a recording, one JSON line each
+    pub fn write_jsonl(entries: &[CacheEntry], mut writer: impl Write) -> Result<(), String> {
+        for entry in entries {
+            let line = serde_json::to_string(entry)
+                .map_err(|e| format!("Failed to serialize cache entry: {}", e))?;
+            writeln!(writer, "{}", line).map_err(|e| format!("Failed to write recording: {}", e))?;
+        }
+        writer.flush().map_err(|e| format!("Failed to write recording: {}", e))
+    }
+
+    /// Number of calls recorded and not yet taken, plus calls left to replay
+    pub fn len(&self) -> usize {
+        self.recorded.len() + self.replay.values().map(VecDeque::len).sum::<usize>()
+    }
+
+    /// Whether there's nothing recorded and nothing left to replay
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take the calls recorded so far, in call order
+    pub fn take_recorded(&mut self) -> Vec<CacheEntry> {
+        std::mem::take(&mut self.recorded)
+    }
+
+    fn record(&mut self, entry: CacheEntry) {
+        self.recorded.push(entry);
+    }
+
+    fn next(&mut self, key: &str) -> Option<CacheEntry> {
+        self.replay.get_mut(key)?.pop_front()
+    }
+}
+
+/// Hash of an External call: URI plus every input's shape, data, and confidence
+///
+/// Every variable-length part is length-prefixed, so the same bytes split
+/// differently across inputs or between shape and data hash differently.
+pub fn input_hash(uri: &str, inputs: &[&Tensor]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update((uri.len() as u64).to_le_bytes());
+    hasher.update(uri.as_bytes());
+    hasher.update((inputs.len() as u64).to_le_bytes());
+
+    for input in inputs {
+        hasher.update((input.shape.len() as u64).to_le_bytes());
+        for dim in &input.shape {
+            hasher.update(dim.to_le_bytes());
+        }
+        hasher.update((input.data.len() as u64).to_le_bytes());
+        for value in &input.data {
+            hasher.update(value.to_le_bytes());
+        }
+        hasher.update(input.confidence.to_le_bytes());
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+/// Resolver that records results from an inner resolver, or replays them
+pub struct CachingResolver {
+    /// Live resolver (None when replaying)
+    inner: Option<Arc<dyn ExternalResolver>>,
+    /// Recorded results
+    cache: Arc<Mutex<ResolverCache>>,
+}
+
+impl CachingResolver {
+    /// Record every result produced by `inner`
+    pub fn recording(inner: Arc<dyn ExternalResolver>) -> Self {
+        Self {
+            inner: Some(inner),
+            cache: Arc::new(Mutex::new(ResolverCache::new())),
+        }
+    }
+
+    /// Serve results only from a previously recorded cache
+    pub fn replaying(cache: ResolverCache) -> Self {
+        Self {
+            inner: None,
+            cache: Arc::new(Mutex::new(cache)),
+        }
+    }
+
+    /// Shared handle to the cache (e.g. to persist what's been recorded)
+    pub fn cache(&self) -> Arc<Mutex<ResolverCache>> {
+        self.cache.clone()
+    }
+}
+
+impl ExternalResolver for CachingResolver {
+    fn resolve(&self, uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+        let key = input_hash(uri, &inputs);
+        let lock = || self.cache.lock().map_err(|_| "Resolver cache poisoned".to_string());
+
+        match &self.inner {
+            Some(inner) => {
+                // Don't hold the cache across the (possibly slow) live call
+                let result = inner.resolve(uri, inputs);
+                let entry = CacheEntry {
+                    key,
+                    uri: uri.to_string(),
+                    result: result.as_ref().map(CachedTensor::from).map_err(Clone::clone),
+                };
+                lock()?.record(entry);
+                result
+            }
+            None => {
+                let entry = lock()?
+                    .next(&key)
+                    .ok_or_else(|| format!("No recorded result for {} (input hash {})", uri, key))?;
+                entry.result.as_ref().map(Tensor::from).map_err(Clone::clone)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolvers::BinanceResolver;
+
+    #[test]
+    fn test_record_then_replay() {
+        let recorder = CachingResolver::recording(Arc::new(BinanceResolver::new()));
+        let live = recorder.resolve("binance:ticker:BTCUSDT", vec![]).unwrap();
+        assert!(recorder.resolve("binance:balance:BTC", vec![]).is_err());
+
+        let again = recorder.resolve("binance:ticker:BTCUSDT", vec![]).unwrap();
+
+        let mut jsonl = Vec::new();
+        let recorded = recorder.cache().lock().unwrap().take_recorded();
+        ResolverCache::write_jsonl(&recorded, &mut jsonl).unwrap();
+        assert!(recorder.cache().lock().unwrap().is_empty());
+        assert_eq!(String::from_utf8_lossy(&jsonl).lines().count(), 3);
+
+        let cache = ResolverCache::read_jsonl(jsonl.as_slice()).unwrap();
+        assert_eq!(cache.len(), 3);
+        let replayer = CachingResolver::replaying(cache);
+
+        let replayed = replayer.resolve("binance:ticker:BTCUSDT", vec![]).unwrap();
+        assert_eq!(replayed.data, live.data);
+        assert_eq!(replayed.confidence, live.confidence);
+        assert!(replayer.resolve("binance:balance:BTC", vec![]).is_err());
+        assert_eq!(replayer.resolve("binance:ticker:BTCUSDT", vec![]).unwrap().data, again.data);
+
+        // Each recorded call replays once
+        assert!(replayer.cache().lock().unwrap().is_empty());
+        assert!(replayer.resolve("binance:ticker:BTCUSDT", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_read_jsonl_rejects_bad_lines() {
+        let err = ResolverCache::read_jsonl("{\"key\": 1}\n".as_bytes()).unwrap_err();
+        assert!(err.contains("line 1"), "{}", err);
+        assert!(ResolverCache::read_jsonl("\n".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_replay_misses_unrecorded_calls() {
+        let replayer = CachingResolver::replaying(ResolverCache::new());
+        let err = replayer.resolve("binance:ticker:BTCUSDT", vec![]).unwrap_err();
+        assert!(err.starts_with("No recorded result"));
+    }
+
+    #[test]
+    fn test_input_hash_depends_on_inputs() {
+        let a = Tensor::scalar(1.0, 1.0);
+        let b = Tensor::scalar(2.0, 1.0);
+
+        assert_eq!(input_hash("x", &[&a]), input_hash("x", &[&a]));
+        assert_ne!(input_hash("x", &[&a]), input_hash("x", &[&b]));
+        assert_ne!(input_hash("x", &[&a]), input_hash("y", &[&a]));
+    }
+
+    #[test]
+    fn test_input_hash_length_prefixes() {
+        // Same bytes, split differently between shape and data
+        let short = Tensor::new(vec![2], vec![3.0], 1.0);
+        let long = Tensor::new(vec![2, 3.0f32.to_bits()], vec![], 1.0);
+        assert_ne!(input_hash("x", &[&short]), input_hash("x", &[&long]));
+
+        // Same bytes, split differently across inputs
+        let a = Tensor::new(vec![], vec![1.0], 1.0);
+        let ab = Tensor::new(vec![], vec![1.0, 1.0, 1.0], 1.0);
+        assert_ne!(input_hash("x", &[&a, &a]), input_hash("x", &[&ab]));
+    }
+
+    /// Resolver that signals when a call starts, then blocks until released
+    struct Gate {
+        entered: Mutex<std::sync::mpsc::Sender<()>>,
+        release: Mutex<std::sync::mpsc::Receiver<()>>,
+    }
+
+    impl ExternalResolver for Gate {
+        fn resolve(&self, _uri: &str, _inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+            self.entered.lock().unwrap().send(()).map_err(|e| e.to_string())?;
+            self.release.lock().unwrap().recv().map_err(|e| e.to_string())?;
+            Ok(Tensor::scalar(1.0, 1.0))
+        }
+    }
+
+    #[test]
+    fn test_cache_unlocked_during_live_call() {
+        let (entered_tx, entered) = std::sync::mpsc::channel();
+        let (release, release_rx) = std::sync::mpsc::channel();
+        let gate = Gate {
+            entered: Mutex::new(entered_tx),
+            release: Mutex::new(release_rx),
+        };
+        let recorder = Arc::new(CachingResolver::recording(Arc::new(gate)));
+
+        let call = {
+            let recorder = recorder.clone();
+            std::thread::spawn(move || recorder.resolve("slow:call", vec![]).is_ok())
+        };
+        entered.recv().unwrap();
+
+        // The call is in flight; the cache must still be readable
+        assert!(recorder.cache().try_lock().unwrap().is_empty());
+        release.send(()).unwrap();
+        assert!(call.join().unwrap());
+        assert_eq!(recorder.cache().lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,391 @@
+//! Connector configuration
+//!
+//! One serializable description of how to build a connector's resolver:
+//! venue, credential references, environment, endpoint overrides, rate-limit
+//! backoff, account selection, and order-safety tuning. Credentials are
+//! referenced by environment variable name so config files never hold
+//! secrets. Options a connector can't honor are errors, not warnings.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use zerolang::ExternalResolver;
+
+use crate::resolvers::{BinanceResolver, Environment, HttpResolver};
+use crate::retry::RetryConfig;
+
+/// Backoff for requests that hit a rate limit or time out; unset fields
+/// keep the `RetryConfig` defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Maximum attempts per request, including the first
+    pub max_attempts: Option<u32>,
+    /// Delay before the first retry, in milliseconds
+    pub base_delay_ms: Option<u64>,
+    /// Upper bound on any single delay, in milliseconds
+    pub max_delay_ms: Option<u64>,
+    /// Growth factor applied per retry (at least 1)
+    pub multiplier: Option<f64>,
+    /// Jitter as a fraction of the delay (0 to 1)
+    pub jitter: Option<f64>,
+}
+
+/// How to build a connector
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConnectorConfig {
+    /// Venue name (e.g. "binance", "okx", "hyperliquid")
+    pub venue: String,
+    /// Environment variable holding the API key
+    pub api_key_env: Option<String>,
+    /// Environment variable holding the API secret
+    pub api_secret_env: Option<String>,
+    /// Mainnet, testnet or demo endpoints
+    #[serde(default)]
+    pub environment: Environment,
+    /// REST base URL override
+    pub base_url: Option<String>,
+    /// Futures REST base URL override (Binance)
+    pub futures_base_url: Option<String>,
+    /// Proxy for HTTP requests
+    pub proxy: Option<String>,
+    /// Per-request timeout in milliseconds
+    pub timeout_ms: Option<u64>,
+    /// Backoff when the venue rate-limits requests
+    #[serde(default)]
+    pub rate_limit: RateLimitConfig,
+    /// Sub-account to trade on (Binance sub-account email, OKX sub-account name)
+    pub subaccount: Option<String>,
+    /// Vault address to trade for (Hyperliquid)
+    pub vault_address: Option<String>,
+    /// Limit price band as a fraction of the last price (Binance): unset
+    /// uses the symbol's PERCENT_PRICE filter, 0 disables the check
+    pub price_band: Option<f32>,
+    /// Maximum estimated market-order slippage in basis points (Binance)
+    pub max_slippage_bps: Option<f32>,
+    /// Reprice crossing limit orders as post-only (Binance)
+    #[serde(default)]
+    pub maker_only: bool,
+}
+
+impl ConnectorConfig {
+    /// Configuration for a venue with all defaults
+    pub fn new(venue: &str) -> Self {
+        Self {
+            venue: venue.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Load a configuration from JSON
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse connector config: {}", e))
+    }
+
+    /// Retry policy for requests to this connector
+    pub fn retry(&self) -> Result<RetryConfig, String> {
+        let limits = &self.rate_limit;
+        let mut retry = RetryConfig::default();
+
+        if let Some(attempts) = limits.max_attempts {
+            if attempts == 0 {
+                return Err("Invalid rate_limit.max_attempts: 0".to_string());
+            }
+            retry = retry.with_max_attempts(attempts);
+        }
+        if let Some(ms) = limits.base_delay_ms {
+            retry = retry.with_base_delay(Duration::from_millis(ms));
+        }
+        if let Some(ms) = limits.max_delay_ms {
+            retry = retry.with_max_delay(Duration::from_millis(ms));
+        }
+        match limits.multiplier {
+            Some(m) if m < 1.0 || !m.is_finite() => {
+                return Err(format!("Invalid rate_limit.multiplier: {}", m));
+            }
+            Some(m) => retry = retry.with_multiplier(m),
+            None => {}
+        }
+        match limits.jitter {
+            Some(j) if !(0.0..=1.0).contains(&j) => {
+                return Err(format!("Invalid rate_limit.jitter: {}", j));
+            }
+            Some(j) => retry = retry.with_jitter(j),
+            None => {}
+        }
+
+        Ok(retry)
+    }
+
+    /// Read the referenced credentials through `env`
+    fn credentials(
+        &self,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Option<(String, String)>, String> {
+        let read = |var: &str| env(var).ok_or_else(|| format!("Environment variable {} is not set", var));
+
+        match (&self.api_key_env, &self.api_secret_env) {
+            (Some(key), Some(secret)) => Ok(Some((read(key)?, read(secret)?))),
+            (None, None) => Ok(None),
+            _ => Err(format!(
+                "Connector {} needs both api_key_env and api_secret_env",
+                self.venue
+            )),
+        }
+    }
+
+    /// Build the resolver serving this connector, reading credentials from
+    /// the process environment
+    pub fn build(&self) -> Result<Arc<dyn ExternalResolver>, String> {
+        self.build_with_env(&|var| std::env::var(var).ok())
+    }
+
+    /// Build the resolver serving this connector, reading credentials through `env`
+    fn build_with_env(
+        &self,
+        env: &dyn Fn(&str) -> Option<String>,
+    ) -> Result<Arc<dyn ExternalResolver>, String> {
+        self.retry()?;
+        // No connector can select an account yet
+        if let Some(subaccount) = &self.subaccount {
+            return Err(format!(
+                "Connector {} doesn't support sub-accounts yet (subaccount: {})",
+                self.venue, subaccount
+            ));
+        }
+        if let Some(vault) = &self.vault_address {
+            return Err(format!(
+                "Connector {} doesn't support vaults yet (vault_address: {})",
+                self.venue, vault
+            ));
+        }
+
+        match self.venue.as_str() {
+            "binance" => Ok(Arc::new(self.build_binance(env)?)),
+            venue if HttpResolver::new().services().contains(&venue) => {
+                Ok(Arc::new(self.build_http()?))
+            }
+            venue => Err(format!("Unknown connector: {}", venue)),
+        }
+    }
+
+    fn build_binance(&self, env: &dyn Fn(&str) -> Option<String>) -> Result<BinanceResolver, String> {
+        // The Binance resolver makes no HTTP requests yet
+        if self.proxy.is_some() || self.timeout_ms.is_some() {
+            return Err("Connector binance doesn't support proxy or timeout_ms yet".to_string());
+        }
+
+        let mut resolver = match self.credentials(env)? {
+            Some((key, secret)) => BinanceResolver::with_credentials(&key, &secret),
+            None => BinanceResolver::new(),
+        }
+        .with_environment(self.environment);
+
+        if let Some(url) = &self.base_url {
+            resolver = resolver.with_base_url(url);
+        }
+        if let Some(url) = &self.futures_base_url {
+            resolver = resolver.with_futures_base_url(url);
+        }
+        match self.price_band {
+            None => {}
+            Some(band) if band < 0.0 || !band.is_finite() => {
+                return Err(format!("Invalid price_band: {}", band));
+            }
+            Some(0.0) => resolver = resolver.without_price_band(),
+            Some(band) => resolver = resolver.with_price_band(band),
+        }
+        match self.max_slippage_bps {
+            None => {}
+            Some(bps) if bps < 0.0 || !bps.is_finite() => {
+                return Err(format!("Invalid max_slippage_bps: {}", bps));
+            }
+            Some(bps) => resolver = resolver.with_max_slippage_bps(bps),
+        }
+        if self.maker_only {
+            resolver = resolver.with_maker_only();
+        }
+
+        Ok(resolver)
+    }
+
+    fn build_http(&self) -> Result<HttpResolver, String> {
+        if self.api_key_env.is_some() || self.api_secret_env.is_some() {
+            return Err(format!(
+                "Connector {} has no authenticated resolver; remove the credential references",
+                self.venue
+            ));
+        }
+        let binance_only = [
+            ("price_band", self.price_band.is_some()),
+            ("max_slippage_bps", self.max_slippage_bps.is_some()),
+            ("maker_only", self.maker_only),
+            ("futures_base_url", self.futures_base_url.is_some()),
+        ];
+        if let Some((option, _)) = binance_only.iter().find(|(_, set)| *set) {
+            return Err(format!("Connector {} doesn't support {}", self.venue, option));
+        }
+
+        let mut resolver = HttpResolver::new().with_environment(self.environment);
+        if let Some(url) = &self.base_url {
+            resolver = resolver.with_base_url(&self.venue, url);
+        }
+        if let Some(proxy) = &self.proxy {
+            resolver = resolver.with_proxy(proxy)?;
+        }
+        if let Some(timeout_ms) = self.timeout_ms {
+            resolver = resolver.with_timeout(Duration::from_millis(timeout_ms))?;
+        }
+
+        Ok(resolver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use zerolang::Tensor;
+
+    /// Environment lookup over a fixed set of variables
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> =
+            vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        move |var| vars.get(var).cloned()
+    }
+
+    fn with_credentials(config: ConnectorConfig) -> ConnectorConfig {
+        ConnectorConfig {
+            api_key_env: Some("BINANCE_API_KEY".to_string()),
+            api_secret_env: Some("BINANCE_API_SECRET".to_string()),
+            ..config
+        }
+    }
+
+    const CREDENTIALS: &[(&str, &str)] = &[("BINANCE_API_KEY", "key"), ("BINANCE_API_SECRET", "secret")];
+
+    #[test]
+    fn test_from_json_defaults() {
+        let config = ConnectorConfig::from_json(r#"{"venue": "binance"}"#).unwrap();
+        assert_eq!(config, ConnectorConfig::new("binance"));
+        assert_eq!(config.environment, Environment::Mainnet);
+
+        let json = r#"{"venue": "okx", "environment": "demo", "timeout_ms": 5000,
+            "rate_limit": {"max_attempts": 3}, "subaccount": "desk-1"}"#;
+        let config = ConnectorConfig::from_json(json).unwrap();
+        assert_eq!(config.environment, Environment::Demo);
+        assert_eq!(config.timeout_ms, Some(5000));
+        assert_eq!(config.rate_limit.max_attempts, Some(3));
+        assert_eq!(config.subaccount.as_deref(), Some("desk-1"));
+
+        assert!(ConnectorConfig::from_json(r#"{"environment": "testnet"}"#).is_err());
+    }
+
+    #[test]
+    fn test_build_binance_with_credentials() {
+        let config = ConnectorConfig {
+            environment: Environment::Testnet,
+            ..with_credentials(ConnectorConfig::new("binance"))
+        };
+        let resolver = config.build_with_env(&env(CREDENTIALS)).unwrap();
+
+        // Authenticated endpoints only work with credentials loaded
+        assert!(resolver.resolve("binance:balance:BTC", vec![]).is_ok());
+        assert!(ConnectorConfig::new("binance")
+            .build_with_env(&env(&[]))
+            .unwrap()
+            .resolve("binance:balance:BTC", vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_price_band_zero_disables_check() {
+        let build = |json: &str| {
+            let config = with_credentials(ConnectorConfig::from_json(json).unwrap());
+            config.build_with_env(&env(CREDENTIALS)).unwrap()
+        };
+
+        // [side, quantity, price, _]: 40000 is 20% under the 50000 placeholder ticker
+        let deep = Tensor::new(vec![4], vec![1.0, 0.1, 40000.0, 0.0], 1.0);
+        let place = |resolver: Arc<dyn ExternalResolver>| {
+            resolver.resolve("binance:order:BTCUSDT", vec![&deep]).is_ok()
+        };
+
+        assert!(place(build(r#"{"venue": "binance"}"#)));
+        assert!(!place(build(r#"{"venue": "binance", "price_band": 0.1}"#)));
+        assert!(place(build(r#"{"venue": "binance", "price_band": 0}"#)));
+    }
+
+    #[test]
+    fn test_retry_from_rate_limit() {
+        let json = r#"{"venue": "binance", "rate_limit": {"max_attempts": 2, "base_delay_ms": 50, "jitter": 0}}"#;
+        let retry = ConnectorConfig::from_json(json).unwrap().retry().unwrap();
+        assert_eq!(retry.max_attempts, 2);
+        assert_eq!(retry.base_delay, Duration::from_millis(50));
+        assert_eq!(retry.jitter, 0.0);
+        assert_eq!(retry.max_delay, RetryConfig::default().max_delay);
+
+        for bad in [
+            RateLimitConfig { max_attempts: Some(0), ..Default::default() },
+            RateLimitConfig { multiplier: Some(0.5), ..Default::default() },
+            RateLimitConfig { jitter: Some(1.5), ..Default::default() },
+        ] {
+            let config = ConnectorConfig {
+                rate_limit: bad,
+                ..ConnectorConfig::new("binance")
+            };
+            assert!(config.retry().is_err());
+            assert!(config.build_with_env(&env(&[])).is_err());
+        }
+    }
+
+    #[test]
+    fn test_build_rejects_bad_configs() {
+        let build = |config: ConnectorConfig| config.build_with_env(&env(&[]));
+        assert!(build(ConnectorConfig::new("kraken")).is_err());
+
+        // Credentials referenced but not set
+        assert!(build(with_credentials(ConnectorConfig::new("binance"))).is_err());
+
+        let half_credentials = ConnectorConfig {
+            api_key_env: Some("BINANCE_API_KEY".to_string()),
+            ..ConnectorConfig::new("binance")
+        };
+        assert!(half_credentials.build_with_env(&env(CREDENTIALS)).is_err());
+
+        let bad_proxy = ConnectorConfig {
+            proxy: Some("http://[::1".to_string()),
+            ..ConnectorConfig::new("okx")
+        };
+        assert!(build(bad_proxy).is_err());
+
+        let negative_band = ConnectorConfig {
+            price_band: Some(-0.1),
+            ..ConnectorConfig::new("binance")
+        };
+        assert!(build(negative_band).is_err());
+
+        let negative_slippage = ConnectorConfig {
+            max_slippage_bps: Some(-5.0),
+            ..ConnectorConfig::new("binance")
+        };
+        assert!(build(negative_slippage).is_err());
+
+        let timeout = ConnectorConfig {
+            timeout_ms: Some(2000),
+            ..ConnectorConfig::new("hyperliquid")
+        };
+        assert!(build(timeout).is_ok());
+    }
+
+    #[test]
+    fn test_build_rejects_unsupported_options() {
+        let build = |json: &str| ConnectorConfig::from_json(json).unwrap().build_with_env(&env(&[]));
+
+        assert!(build(r#"{"venue": "binance", "proxy": "http://127.0.0.1:8080"}"#).is_err());
+        assert!(build(r#"{"venue": "binance", "timeout_ms": 2000}"#).is_err());
+        assert!(build(r#"{"venue": "binance", "subaccount": "desk-1"}"#).is_err());
+        assert!(build(r#"{"venue": "hyperliquid", "vault_address": "0xabc"}"#).is_err());
+        assert!(build(r#"{"venue": "okx", "max_slippage_bps": 5}"#).is_err());
+        assert!(build(r#"{"venue": "okx", "maker_only": true}"#).is_err());
+    }
+}
@@ -2,10 +2,249 @@
 //!
 //! Specialized resolver for Binance API calls with authentication support.
 
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 use zerolang::{ExternalResolver, Tensor};
 
+use crate::resolvers::{Environment, Interval};
+
+/// Maximum depth returned by the spot /api/v3/depth endpoint
+pub const MAX_ORDERBOOK_DEPTH: u32 = 1000;
+
+/// Maximum candles returned by one /api/v3/klines request
+pub const MAX_KLINES: u32 = 1000;
+
+/// Perpetual funding interval (funding settles at 00:00, 08:00 and 16:00 UTC)
+const FUNDING_INTERVAL_SECS: u64 = 8 * 60 * 60;
+
+/// Cross margin level at which Binance issues a margin call
+pub const MARGIN_CALL_LEVEL: f32 = 1.5;
+
+/// Cross margin level at which Binance liquidates the account
+pub const MARGIN_LIQUIDATION_LEVEL: f32 = 1.1;
+
+/// Order status reported for a `batch_order` row that was rejected
+pub const ORDER_STATUS_REJECTED: f32 = -1.0;
+
+/// Orderbook depth walked when estimating fill prices
+const FILL_ESTIMATE_DEPTH: u32 = 100;
+
+/// Placeholder exchangeInfo filters: (symbol, tickSize, stepSize, minQty, minNotional)
+///
+/// This is not Binance's listing: market data is served for any well-formed
+/// symbol, and only order placement needs a symbol's filters.
+const PLACEHOLDER_FILTERS: &[(&str, f32, f32, f32, f32)] = &[
+    ("BTCUSDT", 0.01, 0.00001, 0.00001, 5.0),
+    ("ETHUSDT", 0.01, 0.0001, 0.0001, 5.0),
+    ("BNBUSDT", 0.01, 0.001, 0.001, 5.0),
+    ("SOLUSDT", 0.01, 0.001, 0.001, 5.0),
+    ("XRPUSDT", 0.0001, 0.1, 0.1, 5.0),
+    ("BTCUSDC", 0.01, 0.00001, 0.00001, 5.0),
+    ("ETHUSDC", 0.01, 0.0001, 0.0001, 5.0),
+    ("BTCFDUSD", 0.01, 0.00001, 0.00001, 5.0),
+    ("BTCEUR", 0.01, 0.00001, 0.00001, 5.0),
+    ("ETHBTC", 0.00001, 0.0001, 0.0001, 0.0001),
+    ("BNBBTC", 0.000001, 0.001, 0.001, 0.0001),
+    ("LTCBTC", 0.000001, 0.001, 0.001, 0.0001),
+];
+
+/// Canonicalize a trading pair to Binance's symbol format
+///
+/// Accepts "BTC/USDT", "btc-usdt", "BTC_USDT" or "BTCUSDT" and returns "BTCUSDT".
+/// This only fixes the spelling; order placement additionally needs the
+/// symbol's trading filters (see `BinanceResolver::get_exchange_info`).
+pub fn normalize_pair(pair: &str) -> Result<String, String> {
+    let upper = pair.trim().to_uppercase();
+    let parts: Vec<&str> = upper.split(['/', '-', '_']).collect();
+
+    if !matches!(parts.len(), 1 | 2) {
+        return Err(format!("Invalid pair format: {}", pair));
+    }
+    if parts
+        .iter()
+        .any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        return Err(format!("Invalid pair format: {}", pair));
+    }
+
+    Ok(parts.concat())
+}
+
+/// Binance's name for a candle interval
+pub fn interval_param(interval: Interval) -> &'static str {
+    match interval {
+        Interval::M1 => "1m",
+        Interval::M5 => "5m",
+        Interval::M15 => "15m",
+        Interval::H1 => "1h",
+        Interval::H4 => "4h",
+        Interval::D1 => "1d",
+    }
+}
+
+/// Seconds from a unix timestamp to the next perpetual funding time
+fn seconds_to_next_funding(now_secs: u64) -> u64 {
+    FUNDING_INTERVAL_SECS - now_secs % FUNDING_INTERVAL_SECS
+}
+
+/// Order side encoded in a tensor value: positive = BUY, negative = SELL
+///
+/// Zero is rejected rather than defaulted, so a graph using another
+/// encoding (e.g. 0 = BUY) fails instead of trading the wrong side.
+pub fn order_side(value: f32) -> Result<&'static str, String> {
+    if value > 0.0 {
+        Ok("BUY")
+    } else if value < 0.0 {
+        Ok("SELL")
+    } else {
+        Err(format!("Invalid order side {} (positive = BUY, negative = SELL)", value))
+    }
+}
+
+/// Read an order's side, quantity and limit price (None = market)
+///
+/// Two input forms are accepted: a `side` URI param with inputs
+/// [price, quantity], as the bundled strategy graphs use, or one tensor
+/// [side, quantity, price (>0 = limit), _] with the side encoded as in
+/// `order_side`.
+pub fn order_params(
+    side: Option<&str>,
+    inputs: &[&Tensor],
+) -> Result<(&'static str, f32, Option<f32>), String> {
+    if let (Some(side), [price, quantity]) = (side, inputs) {
+        let side = match side.to_uppercase().as_str() {
+            "BUY" => "BUY",
+            "SELL" => "SELL",
+            _ => return Err(format!("Unknown order side: {}", side)),
+        };
+        let (Some(&price), Some(&quantity)) = (price.data.first(), quantity.data.first()) else {
+            return Err("Invalid order input tensor".to_string());
+        };
+        return Ok((side, quantity, (price > 0.0).then_some(price)));
+    }
+
+    let input = match inputs.first() {
+        Some(input) if input.data.len() >= 4 => input,
+        _ => return Err("Invalid order input tensor".to_string()),
+    };
+
+    let side = order_side(input.data[0])?;
+    let price = if input.data[2] > 0.0 {
+        Some(input.data[2])
+    } else {
+        None
+    };
+    Ok((side, input.data[1], price))
+}
+
+/// Endpoints served by `BinanceResolver` (the `{endpoint}` in "binance:{endpoint}:{params}")
+pub const ENDPOINTS: &[&str] = &[
+    "ticker",
+    "orderbook",
+    "fill_price",
+    "funding",
+    "klines",
+    "mark_price",
+    "exchange_info",
+    "balance",
+    "fees",
+    "order",
+    "batch_order",
+    "margin_account",
+    "margin_balance",
+    "margin_borrow",
+    "margin_repay",
+];
+
+/// Percent-encode a value for an application/x-www-form-urlencoded body
+///
+/// Everything outside the RFC 3986 unreserved set is escaped, so the string
+/// that gets signed is byte-for-byte the string Binance verifies.
+fn form_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Digits after the decimal point in a tick or step size (0.01 -> 2)
+fn decimals(increment: f32) -> usize {
+    increment
+        .to_string()
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len())
+}
+
+/// Snap a value to a multiple of `increment`, rounding down or up
+///
+/// Both are widened through their shortest decimal form and the ratio is
+/// rounded to 6 places first, so binary noise (0.1 / 0.00001 = 9999.999...)
+/// never moves the result by a whole step.
+fn snap(value: f32, increment: f32, up: bool) -> f64 {
+    let widen = |v: f32| v.to_string().parse::<f64>().unwrap_or(v as f64);
+    let steps = (widen(value) / widen(increment) * 1e6).round() / 1e6;
+    let steps = if up { steps.ceil() } else { steps.floor() };
+    steps * increment as f64
+}
+
+/// How far a limit price may sit from the last price
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PriceBand {
+    /// The symbol's PERCENT_PRICE filter (multiplierDown..multiplierUp)
+    Exchange,
+    /// ±fraction of the last price, never wider than PERCENT_PRICE
+    Fraction(f32),
+    /// No local check (the venue still enforces PERCENT_PRICE)
+    Disabled,
+}
+
+/// Trading limits for one symbol (from the /api/v3/exchangeInfo filters)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExchangeInfo {
+    /// Exchange symbol (e.g. "BTCUSDT")
+    pub symbol: String,
+    /// PRICE_FILTER tickSize
+    pub tick_size: f32,
+    /// LOT_SIZE stepSize
+    pub step_size: f32,
+    /// LOT_SIZE minQty
+    pub min_qty: f32,
+    /// NOTIONAL minNotional
+    pub min_notional: f32,
+    /// PERCENT_PRICE multiplierUp
+    pub multiplier_up: f32,
+    /// PERCENT_PRICE multiplierDown
+    pub multiplier_down: f32,
+    /// MAX_NUM_ORDERS maxNumOrders
+    pub max_num_orders: u32,
+}
+
+/// An order that passed the pre-trade checks, rounded to the symbol's filters
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidatedOrder {
+    /// "BUY" or "SELL"
+    pub side: &'static str,
+    /// "MARKET", "LIMIT" or "LIMIT_MAKER"
+    pub order_type: &'static str,
+    /// Quantity rounded down to the step size
+    pub quantity: f32,
+    /// Limit price rounded to the tick size (and repriced if maker-only)
+    pub price: Option<f32>,
+    /// Quantity formatted with the step size's decimals, as sent
+    pub quantity_param: String,
+    /// Price formatted with the tick size's decimals, as sent
+    pub price_param: Option<String>,
+}
+
 /// Binance API resolver
 pub struct BinanceResolver {
     /// API key (optional, for authenticated requests)
@@ -16,8 +255,16 @@ pub struct BinanceResolver {
     base_url: String,
     /// Base URL for futures API
     futures_base_url: String,
-    /// Testnet mode
-    testnet: bool,
+    /// Trading environment
+    environment: Environment,
+    /// Allowed deviation of a limit price from the last price
+    price_band: PriceBand,
+    /// Maximum estimated slippage for market orders, in basis points of mid
+    max_slippage_bps: Option<f32>,
+    /// Reprice crossing limit orders instead of letting them take
+    maker_only: bool,
+    /// Trading limits per symbol, loaded on first use
+    exchange_info: Mutex<Option<HashMap<String, ExchangeInfo>>>,
 }
 
 impl BinanceResolver {
@@ -28,7 +275,11 @@ impl BinanceResolver {
             api_secret: None,
             base_url: "https://api.binance.com".to_string(),
             futures_base_url: "https://fapi.binance.com".to_string(),
-            testnet: false,
+            environment: Environment::Mainnet,
+            price_band: PriceBand::Exchange,
+            max_slippage_bps: None,
+            maker_only: false,
+            exchange_info: Mutex::new(None),
         }
     }
 
@@ -39,15 +290,86 @@ impl BinanceResolver {
             api_secret: Some(api_secret.to_string()),
             base_url: "https://api.binance.com".to_string(),
             futures_base_url: "https://fapi.binance.com".to_string(),
-            testnet: false,
+            environment: Environment::Mainnet,
+            price_band: PriceBand::Exchange,
+            max_slippage_bps: None,
+            maker_only: false,
+            exchange_info: Mutex::new(None),
         }
     }
 
     /// Use testnet endpoints
-    pub fn testnet(mut self) -> Self {
-        self.base_url = "https://testnet.binance.vision".to_string();
-        self.futures_base_url = "https://testnet.binancefuture.com".to_string();
-        self.testnet = true;
+    pub fn testnet(self) -> Self {
+        self.with_environment(Environment::Testnet)
+    }
+
+    /// Default REST and futures REST base URLs of an environment
+    fn environment_urls(environment: Environment) -> (&'static str, &'static str) {
+        match environment {
+            Environment::Mainnet => ("https://api.binance.com", "https://fapi.binance.com"),
+            Environment::Testnet => (
+                "https://testnet.binance.vision",
+                "https://testnet.binancefuture.com",
+            ),
+            Environment::Demo => ("https://demo-api.binance.com", "https://demo-fapi.binance.com"),
+        }
+    }
+
+    /// Select mainnet, testnet or demo-trading endpoints
+    ///
+    /// Base URLs set with `with_base_url`/`with_futures_base_url` are kept;
+    /// only URLs still at the previous environment's defaults are switched.
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        let (old_base, old_futures) = Self::environment_urls(self.environment);
+        let (base_url, futures_base_url) = Self::environment_urls(environment);
+
+        if self.base_url == old_base {
+            self.base_url = base_url.to_string();
+        }
+        if self.futures_base_url == old_futures {
+            self.futures_base_url = futures_base_url.to_string();
+        }
+        self.environment = environment;
+        self
+    }
+
+    /// Override the REST base URL (mirror domains, regional endpoints)
+    pub fn with_base_url(mut self, url: &str) -> Self {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Override the futures REST base URL
+    pub fn with_futures_base_url(mut self, url: &str) -> Self {
+        self.futures_base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Tighten the price band to a fraction of the last price (0.05 = ±5%)
+    ///
+    /// The band defaults to the symbol's PERCENT_PRICE filter; a fraction
+    /// wider than that filter is clamped to it.
+    pub fn with_price_band(mut self, band: f32) -> Self {
+        self.price_band = PriceBand::Fraction(band);
+        self
+    }
+
+    /// Disable the price band check (for strategies that intentionally post deep)
+    pub fn without_price_band(mut self) -> Self {
+        self.price_band = PriceBand::Disabled;
+        self
+    }
+
+    /// Reject market orders whose estimated fill is more than `bps` from mid
+    pub fn with_max_slippage_bps(mut self, bps: f32) -> Self {
+        self.max_slippage_bps = Some(bps);
+        self
+    }
+
+    /// Place limit orders as LIMIT_MAKER, repricing any that would cross the
+    /// book to one tick behind the opposite best price instead of being rejected
+    pub fn with_maker_only(mut self) -> Self {
+        self.maker_only = true;
         self
     }
 
@@ -100,6 +422,8 @@ impl BinanceResolver {
 
     /// Get orderbook
     fn get_orderbook(&self, symbol: &str, limit: u32) -> Result<Tensor, String> {
+        // Requesting more than the venue serves only wastes request weight
+        let limit = limit.clamp(1, MAX_ORDERBOOK_DEPTH);
         tracing::info!("Binance: Getting orderbook for {} (limit: {})", symbol, limit);
         
         // Placeholder: Return simulated orderbook
@@ -125,6 +449,181 @@ impl BinanceResolver {
         ))
     }
 
+    /// Estimate the average fill price of a market order by walking the book
+    pub fn estimate_fill_price(&self, symbol: &str, side: &str, size: f32) -> Result<f32, String> {
+        if size <= 0.0 {
+            return Err(format!("Invalid order size: {}", size));
+        }
+
+        let book = self.get_orderbook(symbol, FILL_ESTIMATE_DEPTH)?;
+        let levels = book.data.len() / 4;
+        let (bids, asks) = book.data.split_at(levels * 2);
+
+        // Buys consume asks, sells consume bids
+        let book_side = match side.to_uppercase().as_str() {
+            "BUY" => asks,
+            "SELL" => bids,
+            _ => return Err(format!("Unknown order side: {}", side)),
+        };
+
+        let mut remaining = size;
+        let mut notional = 0.0;
+        for level in book_side.chunks_exact(2) {
+            let (price, quantity) = (level[0], level[1]);
+            let filled = remaining.min(quantity);
+            notional += filled * price;
+            remaining -= filled;
+            if remaining <= 0.0 {
+                return Ok(notional / size);
+            }
+        }
+
+        Err(format!(
+            "Insufficient {} depth to fill {} (short by {})",
+            symbol, size, remaining
+        ))
+    }
+
+    /// Get OHLCV candles, oldest first
+    fn get_klines(&self, symbol: &str, interval: Interval, limit: u32) -> Result<Tensor, String> {
+        let limit = limit.clamp(1, MAX_KLINES);
+        tracing::info!(
+            "Binance: Getting {} klines for {} (limit: {})",
+            interval_param(interval),
+            symbol,
+            limit
+        );
+
+        // Placeholder: Return simulated candles, oldest first, closing at the ticker price
+        // In production: fetch from https://api.binance.com/api/v3/klines?symbol={symbol}&interval={interval}&limit={limit}
+        // Shape: [limit, 5] - each row [open, high, low, close, volume]
+        let mut data = Vec::new();
+        for i in 0..limit {
+            let close = 50000.0 - ((limit - 1 - i) as f32 * 10.0);
+            data.push(close - 5.0); // open
+            data.push(close + 10.0); // high
+            data.push(close - 15.0); // low
+            data.push(close); // close
+            data.push(1.0 + (i as f32 * 0.1)); // volume
+        }
+
+        Ok(Tensor::new(vec![limit, 5], data, 0.5))
+    }
+
+    /// Get perpetual mark and index price
+    fn get_mark_price(&self, symbol: &str) -> Result<Tensor, String> {
+        tracing::info!("Binance: Getting mark price for {}", symbol);
+
+        // Placeholder: Return simulated premium index
+        // In production: fetch from https://fapi.binance.com/fapi/v1/premiumIndex?symbol={symbol}
+        Ok(Tensor::new(
+            vec![2],
+            vec![50005.0, 50000.0], // [mark_price, index_price]
+            0.5,
+        ))
+    }
+
+    /// Get the current funding rate, time to next funding, and mark price
+    fn get_funding_rate(&self, symbol: &str) -> Result<Tensor, String> {
+        tracing::info!("Binance: Getting funding rate for {}", symbol);
+
+        let mark_price = self.get_mark_price(symbol)?.data[0];
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| e.to_string())?
+            .as_secs();
+
+        // Placeholder: Return the default 0.01% rate
+        // In production: read lastFundingRate and nextFundingTime from
+        // https://fapi.binance.com/fapi/v1/premiumIndex?symbol={symbol}
+        Ok(Tensor::new(
+            vec![3],
+            vec![
+                0.0001, // funding rate
+                seconds_to_next_funding(now) as f32,
+                mark_price,
+            ],
+            0.5,
+        ))
+    }
+
+    /// Fetch trading limits for every symbol exchangeInfo describes
+    fn load_exchange_info(&self) -> HashMap<String, ExchangeInfo> {
+        tracing::info!("Binance: Getting exchange info");
+
+        // Placeholder: Return per-symbol filters for a fixed set of symbols,
+        // with the PERCENT_PRICE and MAX_NUM_ORDERS values spot symbols share
+        // In production: fetch from https://api.binance.com/api/v3/exchangeInfo
+        // and keep the symbols with status TRADING
+        PLACEHOLDER_FILTERS
+            .iter()
+            .map(|&(symbol, tick_size, step_size, min_qty, min_notional)| {
+                let info = ExchangeInfo {
+                    symbol: symbol.to_string(),
+                    tick_size,
+                    step_size,
+                    min_qty,
+                    min_notional,
+                    multiplier_up: 5.0,
+                    multiplier_down: 0.2,
+                    max_num_orders: 200,
+                };
+                (symbol.to_string(), info)
+            })
+            .collect()
+    }
+
+    /// Get trading limits for a symbol, loading exchangeInfo once and caching it
+    ///
+    /// Symbols without known filters are rejected, since orders on them
+    /// can't be rounded or validated.
+    pub fn get_exchange_info(&self, symbol: &str) -> Result<ExchangeInfo, String> {
+        let mut cache = self
+            .exchange_info
+            .lock()
+            .map_err(|_| "Exchange info cache poisoned".to_string())?;
+
+        cache
+            .get_or_insert_with(|| self.load_exchange_info())
+            .get(symbol)
+            .cloned()
+            .ok_or_else(|| format!("No exchange info for {}: trading filters are unknown", symbol))
+    }
+
+    /// Normalized symbol from URI params (defaults to BTCUSDT)
+    fn symbol_param(&self, params: &HashMap<String, String>) -> Result<String, String> {
+        normalize_pair(params.get("symbol").map(|s| s.as_str()).unwrap_or("BTCUSDT"))
+    }
+
+    /// Reject orders below the symbol's minimum quantity or notional
+    fn check_order_limits(
+        &self,
+        info: &ExchangeInfo,
+        quantity: f32,
+        price: Option<f32>,
+    ) -> Result<(), String> {
+        if quantity < info.min_qty {
+            return Err(format!(
+                "Order quantity {} for {} is below minimum {}",
+                quantity, info.symbol, info.min_qty
+            ));
+        }
+
+        let reference_price = match price {
+            Some(price) => price,
+            None => self.get_ticker(&info.symbol)?.data[0],
+        };
+        let notional = quantity * reference_price;
+        if notional < info.min_notional {
+            return Err(format!(
+                "Order notional {} for {} is below minimum {}",
+                notional, info.symbol, info.min_notional
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Get account balance (requires authentication)
     fn get_balance(&self, asset: &str) -> Result<Tensor, String> {
         if self.api_key.is_none() {
@@ -141,23 +640,314 @@ impl BinanceResolver {
         ))
     }
 
+    /// Get maker/taker fee rates for the account (requires authentication)
+    fn get_fee_rates(&self, symbol: &str) -> Result<Tensor, String> {
+        if self.api_key.is_none() {
+            return Err("API credentials required for fee rates".to_string());
+        }
+
+        tracing::info!("Binance: Getting fee rates for {}", symbol);
+
+        // Placeholder: Return default VIP 0 rates
+        // In production: fetch from https://api.binance.com/sapi/v1/asset/tradeFee?symbol={symbol}
+        Ok(Tensor::new(
+            vec![2],
+            vec![0.001, 0.001], // [maker, taker]
+            0.5,
+        ))
+    }
+
+    /// Get the cross margin account summary (requires authentication)
+    ///
+    /// Returns [total_asset_btc, total_liability_btc, margin_level,
+    /// liquidation_headroom], where the headroom is the margin level's distance
+    /// above `MARGIN_LIQUIDATION_LEVEL`, for graphs to branch on. A margin
+    /// level below `MARGIN_CALL_LEVEL` is logged as a warning.
+    fn get_margin_account(&self) -> Result<Tensor, String> {
+        if self.api_key.is_none() {
+            return Err("API credentials required for margin account".to_string());
+        }
+
+        tracing::info!("Binance: Getting margin account");
+
+        // Placeholder: Return a lightly levered account
+        // In production: fetch from https://api.binance.com/sapi/v1/margin/account
+        let (total_asset, total_liability) = (2.0, 0.5);
+        let margin_level = total_asset / total_liability;
+
+        if margin_level < MARGIN_CALL_LEVEL {
+            tracing::warn!(
+                "Binance: Margin level {:.2} is below the margin call level {} (liquidation at {})",
+                margin_level, MARGIN_CALL_LEVEL, MARGIN_LIQUIDATION_LEVEL
+            );
+        }
+
+        Ok(Tensor::new(
+            vec![4],
+            vec![
+                total_asset,
+                total_liability,
+                margin_level,
+                margin_level - MARGIN_LIQUIDATION_LEVEL,
+            ],
+            0.5,
+        ))
+    }
+
+    /// Get one asset's margin balance including borrowed amounts (requires authentication)
+    fn get_margin_balance(&self, asset: &str) -> Result<Tensor, String> {
+        if self.api_key.is_none() {
+            return Err("API credentials required for margin balance".to_string());
+        }
+
+        tracing::info!("Binance: Getting margin balance for {}", asset);
+
+        // Placeholder: Return simulated balance
+        // In production: read userAssets from https://api.binance.com/sapi/v1/margin/account
+        Ok(Tensor::new(
+            vec![4],
+            vec![1.5, 0.0, 0.5, 0.0001], // [free, locked, borrowed, interest]
+            0.5,
+        ))
+    }
+
+    /// Borrow or repay a margin asset (requires authentication)
+    ///
+    /// Not implemented: a simulated transfer would report borrowed funds that
+    /// don't exist, so requests are validated and then refused.
+    fn margin_transfer(&self, kind: &str, asset: &str, amount: f32) -> Result<Tensor, String> {
+        if self.api_key.is_none() {
+            return Err(format!("API credentials required for margin {}", kind));
+        }
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(format!("Invalid margin {} amount: {}", kind, amount));
+        }
+
+        // In production: POST {base_url}/sapi/v1/margin/{borrow|repay} with a
+        // signed body of asset, amount and timestamp
+        Err(format!(
+            "Binance margin {} of {} {} is not implemented yet",
+            kind, amount, asset
+        ))
+    }
+
+    /// Reject limit prices outside the price band around the last price
+    fn check_price_band(&self, info: &ExchangeInfo, price: f32) -> Result<(), String> {
+        let (down, up) = match self.price_band {
+            PriceBand::Disabled => return Ok(()),
+            PriceBand::Exchange => (info.multiplier_down, info.multiplier_up),
+            PriceBand::Fraction(band) => (
+                (1.0 - band).max(info.multiplier_down),
+                (1.0 + band).min(info.multiplier_up),
+            ),
+        };
+
+        let last = self.get_ticker(&info.symbol)?.data[0];
+        if last <= 0.0 {
+            return Err(format!("No valid last price for {}", info.symbol));
+        }
+
+        if price < last * down || price > last * up {
+            return Err(format!(
+                "Order price {} for {} is outside {}..{} (x{}..x{} of last price {})",
+                price,
+                info.symbol,
+                last * down,
+                last * up,
+                down,
+                up,
+                last
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Reject market orders whose estimated fill price slips too far from mid
+    fn check_slippage(&self, symbol: &str, side: &str, quantity: f32) -> Result<(), String> {
+        let max_bps = match self.max_slippage_bps {
+            Some(max_bps) => max_bps,
+            None => return Ok(()),
+        };
+
+        let top = self.get_orderbook(symbol, 1)?;
+        let (best_bid, best_ask) = (top.data[0], top.data[2]);
+        let mid = (best_bid + best_ask) / 2.0;
+        if mid <= 0.0 {
+            return Err(format!("No valid mid price for {}", symbol));
+        }
+
+        let fill = self.estimate_fill_price(symbol, side, quantity)?;
+        let slippage_bps = (fill - mid).abs() / mid * 10_000.0;
+        if slippage_bps > max_bps {
+            tracing::warn!(
+                "Binance: Skipping {} market order for {} {}: estimated slippage {:.1} bps exceeds {:.1} bps",
+                side, quantity, symbol, slippage_bps, max_bps
+            );
+            return Err(format!(
+                "Estimated slippage {:.1} bps for {} {} exceeds limit of {:.1} bps",
+                slippage_bps, quantity, symbol, max_bps
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Move a limit price that would cross the book to the best maker price
+    fn maker_price(&self, symbol: &str, side: &str, price: f32, tick: f32) -> Result<f32, String> {
+        let top = self.get_orderbook(symbol, 1)?;
+        let (best_bid, best_ask) = (top.data[0], top.data[2]);
+
+        let repriced = match side {
+            "BUY" if price >= best_ask => best_ask - tick,
+            "SELL" if price <= best_bid => best_bid + tick,
+            _ => return Ok(price),
+        };
+        let repriced = (repriced / tick).round() * tick;
+
+        tracing::info!(
+            "Binance: Repriced maker-only {} {} from {} to {}",
+            side, symbol, price, repriced
+        );
+        Ok(repriced)
+    }
+
+    /// Build a signed x-www-form-urlencoded request body
+    ///
+    /// The HMAC-SHA256 signature covers the encoded parameters exactly as sent.
+    fn signed_body(&self, params: &[(&str, String)]) -> Result<String, String> {
+        let secret = self
+            .api_secret
+            .as_ref()
+            .ok_or("API credentials required for signed requests")?;
+
+        let payload = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, form_encode(value)))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+            .map_err(|e| format!("Invalid API secret: {}", e))?;
+        mac.update(payload.as_bytes());
+        let signature = hex::encode(mac.finalize().into_bytes());
+
+        Ok(format!("{}&signature={}", payload, signature))
+    }
+
+    /// Run the pre-trade checks on an order without placing it
+    ///
+    /// Applies maker-only repricing and tick/step rounding, then checks the
+    /// minimum quantity and notional and either the price band (limit
+    /// orders) or the slippage limit (market orders). Symbols without
+    /// exchange info are rejected.
+    pub fn validate_order(
+        &self,
+        symbol: &str,
+        side: &'static str,
+        quantity: f32,
+        price: Option<f32>,
+    ) -> Result<ValidatedOrder, String> {
+        let info = self.get_exchange_info(symbol)?;
+
+        let price = match price {
+            Some(price) if self.maker_only => {
+                Some(self.maker_price(symbol, side, price, info.tick_size)?)
+            }
+            price => price,
+        };
+
+        // Sent values must be multiples of LOT_SIZE stepSize and PRICE_FILTER
+        // tickSize; buys round down and sells up so rounding never crosses further
+        let quantity = snap(quantity, info.step_size, false) as f32;
+        let quantity_param = format!("{:.*}", decimals(info.step_size), quantity);
+        let price = price.map(|price| snap(price, info.tick_size, side == "SELL") as f32);
+        let price_param = price.map(|price| format!("{:.*}", decimals(info.tick_size), price));
+
+        self.check_order_limits(&info, quantity, price)?;
+        match price {
+            Some(price) => self.check_price_band(&info, price)?,
+            None => self.check_slippage(symbol, side, quantity)?,
+        }
+
+        let order_type = match price {
+            None => "MARKET",
+            Some(_) if self.maker_only => "LIMIT_MAKER",
+            Some(_) => "LIMIT",
+        };
+
+        Ok(ValidatedOrder {
+            side,
+            order_type,
+            quantity,
+            price,
+            quantity_param,
+            price_param,
+        })
+    }
+
     /// Place an order (requires authentication)
+    ///
+    /// With `test` set the order is validated like a real one but not sent
+    /// (as /api/v3/order/test does), and no credentials are needed; the
+    /// response reports it resting unfilled with order id 0.
     fn place_order(
         &self,
         symbol: &str,
-        side: &str,
+        side: &'static str,
         quantity: f32,
         price: Option<f32>,
+        client_order_id: Option<&str>,
+        test: bool,
     ) -> Result<Tensor, String> {
-        if self.api_key.is_none() {
+        if self.api_key.is_none() && !test {
             return Err("API credentials required for placing orders".to_string());
         }
 
+        let order = self.validate_order(symbol, side, quantity, price)?;
+        if test {
+            tracing::info!(
+                "Binance: Test {} {} order for {} {} @ {:?} passed validation",
+                side, order.order_type, order.quantity, symbol, order.price
+            );
+            return Ok(Tensor::new(
+                vec![4],
+                vec![0.0, 1.0, 0.0, order.price.unwrap_or(0.0)],
+                0.5,
+            ));
+        }
+
         tracing::info!(
-            "Binance: Placing {} order for {} {} @ {:?}",
-            side, quantity, symbol, price
+            "Binance: Placing {} {} order for {} {} @ {:?}",
+            side, order.order_type, order.quantity, symbol, order.price
         );
 
+        let mut order_params = vec![
+            ("symbol", symbol.to_string()),
+            ("side", side.to_string()),
+            ("type", order.order_type.to_string()),
+            ("quantity", order.quantity_param),
+        ];
+        if let Some(price) = order.price_param {
+            order_params.push(("price", price));
+            if order.order_type == "LIMIT" {
+                order_params.push(("timeInForce", "GTC".to_string()));
+            }
+        }
+        if let Some(id) = client_order_id {
+            order_params.push(("newClientOrderId", id.to_string()));
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| e.to_string())?
+            .as_millis();
+        order_params.push(("timestamp", timestamp.to_string()));
+
+        let body = self.signed_body(&order_params)?;
+        tracing::debug!("Binance: Order body: {}", body);
+        // In production: POST {base_url}/api/v3/order with `body` as
+        // application/x-www-form-urlencoded
+
         // Placeholder: Return simulated order response
         // [order_id, status, filled_qty, avg_price]
         Ok(Tensor::new(
@@ -166,11 +956,83 @@ impl BinanceResolver {
                 12345.0,   // order_id (simulated)
                 1.0,       // status: 1.0 = open
                 0.0,       // filled_qty
-                price.unwrap_or(0.0),
+                order.price.unwrap_or(0.0),
             ],
             0.5,
         ))
     }
+
+    /// Place a set of limit orders on one symbol (requires authentication
+    /// unless `test` is set, see `place_order`)
+    ///
+    /// Spot has no batch order endpoint, so this sends one /api/v3/order
+    /// request per order. `prices` and `sides` (encoded as in `order_side`:
+    /// positive = BUY, negative = SELL) hold one value per order; `sizes` holds one value per order or a single size
+    /// for all of them.
+    ///
+    /// Returns a [N, 4] tensor whose rows line up with the prices. A rejected
+    /// order gets status `ORDER_STATUS_REJECTED` and zeros elsewhere, so one
+    /// bad order doesn't fail the rest.
+    fn place_orders(
+        &self,
+        symbol: &str,
+        prices: &Tensor,
+        sides: &Tensor,
+        sizes: &Tensor,
+        client_order_ids: Option<&[&str]>,
+        test: bool,
+    ) -> Result<Tensor, String> {
+        if self.api_key.is_none() && !test {
+            return Err("API credentials required for placing orders".to_string());
+        }
+
+        let count = prices.data.len();
+        if count == 0 {
+            return Err("batch_order requires at least one price".to_string());
+        }
+        if sides.data.len() != count || !(sizes.data.len() == 1 || sizes.data.len() == count) {
+            return Err(format!(
+                "batch_order expects {} sides and 1 or {} sizes, got {} and {}",
+                count,
+                count,
+                sides.data.len(),
+                sizes.data.len()
+            ));
+        }
+
+        if client_order_ids.is_some_and(|ids| ids.len() != count) {
+            return Err(format!("batch_order expects {} client order IDs", count));
+        }
+
+        let info = self.get_exchange_info(symbol)?;
+        if count > info.max_num_orders as usize {
+            return Err(format!(
+                "{} orders exceed the {} open order limit for {}",
+                count, info.max_num_orders, symbol
+            ));
+        }
+
+        tracing::info!("Binance: Placing {} orders for {} one by one", count, symbol);
+
+        let mut data = Vec::with_capacity(count * 4);
+        for index in 0..count {
+            let size = sizes.data[if sizes.data.len() == 1 { 0 } else { index }];
+            let client_order_id = client_order_ids.map(|ids| ids[index]);
+            let result = order_side(sides.data[index]).and_then(|side| {
+                self.place_order(symbol, side, size, Some(prices.data[index]), client_order_id, test)
+            });
+
+            match result {
+                Ok(response) => data.extend_from_slice(&response.data[..4]),
+                Err(e) => {
+                    tracing::warn!("Binance: Order {} of {} rejected: {}", index, count, e);
+                    data.extend_from_slice(&[0.0, ORDER_STATUS_REJECTED, 0.0, 0.0]);
+                }
+            }
+        }
+
+        Ok(Tensor::new(vec![count as u32, 4], data, 0.5))
+    }
 }
 
 impl Default for BinanceResolver {
@@ -185,37 +1047,109 @@ impl ExternalResolver for BinanceResolver {
 
         match endpoint.as_str() {
             "ticker" => {
-                let symbol = params.get("symbol").map(|s| s.as_str()).unwrap_or("BTCUSDT");
+                let symbol = &self.symbol_param(&params)?;
                 self.get_ticker(symbol)
             }
             "orderbook" | "depth" => {
-                let symbol = params.get("symbol").map(|s| s.as_str()).unwrap_or("BTCUSDT");
+                let symbol = &self.symbol_param(&params)?;
                 let limit = params
                     .get("limit")
                     .and_then(|l| l.parse().ok())
                     .unwrap_or(10);
                 self.get_orderbook(symbol, limit)
             }
+            "fill_price" => {
+                let symbol = &self.symbol_param(&params)?;
+                let side = params.get("side").map(|s| s.as_str()).unwrap_or("BUY");
+                let size = params
+                    .get("size")
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| "fill_price requires a numeric size".to_string())?;
+                let price = self.estimate_fill_price(symbol, side, size)?;
+                Ok(Tensor::scalar(price, 0.5))
+            }
+            "klines" | "candles" => {
+                let symbol = &self.symbol_param(&params)?;
+                let interval = params
+                    .get("interval")
+                    .map(|i| i.parse())
+                    .transpose()?
+                    .unwrap_or(Interval::M1);
+                let limit = params
+                    .get("limit")
+                    .and_then(|l| l.parse().ok())
+                    .unwrap_or(100);
+                self.get_klines(symbol, interval, limit)
+            }
+            "funding" | "funding_rate" => {
+                let symbol = &self.symbol_param(&params)?;
+                self.get_funding_rate(symbol)
+            }
+            "mark_price" | "premium_index" => {
+                let symbol = &self.symbol_param(&params)?;
+                self.get_mark_price(symbol)
+            }
+            "exchange_info" => {
+                let info = self.get_exchange_info(&self.symbol_param(&params)?)?;
+                Ok(Tensor::new(
+                    vec![4],
+                    vec![info.tick_size, info.step_size, info.min_qty, info.min_notional],
+                    0.5,
+                ))
+            }
             "balance" => {
                 let asset = params.get("asset").map(|s| s.as_str()).unwrap_or("BTC");
                 self.get_balance(asset)
             }
+            "fees" | "trade_fee" => {
+                let symbol = &self.symbol_param(&params)?;
+                self.get_fee_rates(symbol)
+            }
+            "margin_account" => self.get_margin_account(),
+            "margin_balance" => {
+                let asset = params.get("asset").map(|s| s.as_str()).unwrap_or("BTC");
+                self.get_margin_balance(asset)
+            }
+            "margin_borrow" | "margin_repay" => {
+                let kind = endpoint.trim_start_matches("margin_");
+                let asset = params.get("asset").map(|s| s.as_str()).unwrap_or("BTC");
+                // Amount from the URI, or from the first input for graph-computed amounts
+                let amount = match params.get("amount") {
+                    Some(amount) => amount
+                        .parse()
+                        .map_err(|_| format!("Invalid margin {} amount: {}", kind, amount))?,
+                    None => inputs
+                        .first()
+                        .and_then(|t| t.data.first().copied())
+                        .ok_or_else(|| format!("margin_{} requires an amount", kind))?,
+                };
+                self.margin_transfer(kind, asset, amount)
+            }
             "order" => {
-                // Extract order params from input tensor
-                if let Some(input) = inputs.first() {
-                    if input.data.len() >= 4 {
-                        let symbol = params.get("symbol").map(|s| s.as_str()).unwrap_or("BTCUSDT");
-                        let side = if input.data[0] > 0.0 { "BUY" } else { "SELL" };
-                        let quantity = input.data[1];
-                        let price = if input.data[2] > 0.0 {
-                            Some(input.data[2])
-                        } else {
-                            None
-                        };
-                        return self.place_order(symbol, side, quantity, price);
-                    }
+                let side = params.get("side").map(|s| s.as_str());
+                let (side, quantity, price) = order_params(side, &inputs)?;
+                let symbol = &self.symbol_param(&params)?;
+                let client_order_id = params.get("client_order_id").map(|s| s.as_str());
+                let test = params.get("test").is_some_and(|t| t == "true");
+                self.place_order(symbol, side, quantity, price, client_order_id, test)
+            }
+            "batch_order" => {
+                let symbol = &self.symbol_param(&params)?;
+                let test = params.get("test").is_some_and(|t| t == "true");
+                // One client order ID per order, '.'-separated
+                let client_order_ids: Option<Vec<&str>> =
+                    params.get("client_order_ids").map(|ids| ids.split('.').collect());
+                match inputs.as_slice() {
+                    [prices, sides, sizes] => self.place_orders(
+                        symbol,
+                        prices,
+                        sides,
+                        sizes,
+                        client_order_ids.as_deref(),
+                        test,
+                    ),
+                    _ => Err("batch_order expects inputs [prices, sides, sizes]".to_string()),
                 }
-                Err("Invalid order input tensor".to_string())
             }
             _ => Err(format!("Unknown Binance endpoint: {}", endpoint)),
         }
@@ -280,6 +1214,13 @@ mod tests {
         assert_eq!(tensor.shape, vec![10, 2]); // 5 bids + 5 asks, each with price and qty
     }
 
+    #[test]
+    fn test_get_mark_price() {
+        let resolver = BinanceResolver::new();
+        let tensor = resolver.resolve("binance:mark_price:BTCUSDT", vec![]).unwrap();
+        assert_eq!(tensor.shape, vec![2]); // mark, index
+    }
+
     #[test]
     fn test_unauthenticated_balance_fails() {
         let resolver = BinanceResolver::new();
@@ -287,4 +1228,447 @@ mod tests {
         
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_get_fee_rates() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+        let tensor = resolver.resolve("binance:fees:BTCUSDT", vec![]).unwrap();
+        assert_eq!(tensor.shape, vec![2]); // maker, taker
+
+        let unauthenticated = BinanceResolver::new();
+        assert!(unauthenticated.resolve("binance:fees:BTCUSDT", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_price_band() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+
+        // [side, quantity, price, _] against the 50000 placeholder ticker
+        let near = Tensor::new(vec![4], vec![1.0, 0.1, 49000.0, 0.0], 1.0);
+        assert!(resolver.resolve("binance:order:BTCUSDT", vec![&near]).is_ok());
+
+        // The default band is PERCENT_PRICE: x0.2..x5 of last price
+        let deep = Tensor::new(vec![4], vec![1.0, 0.1, 30000.0, 0.0], 1.0);
+        assert!(resolver.resolve("binance:order:BTCUSDT", vec![&deep]).is_ok());
+        let far = Tensor::new(vec![4], vec![1.0, 0.1, 9000.0, 0.0], 1.0);
+        assert!(resolver.resolve("binance:order:BTCUSDT", vec![&far]).is_err());
+
+        let tight = BinanceResolver::with_credentials("key", "secret").with_price_band(0.10);
+        assert!(tight.resolve("binance:order:BTCUSDT", vec![&near]).is_ok());
+        assert!(tight.resolve("binance:order:BTCUSDT", vec![&deep]).is_err());
+
+        // A fraction wider than PERCENT_PRICE is clamped to it
+        let wide = BinanceResolver::with_credentials("key", "secret").with_price_band(10.0);
+        assert!(wide.resolve("binance:order:BTCUSDT", vec![&far]).is_err());
+
+        let ungated = resolver.without_price_band();
+        assert!(ungated.resolve("binance:order:BTCUSDT", vec![&far]).is_ok());
+    }
+
+    #[test]
+    fn test_order_rounded_to_filters() {
+        assert_eq!(decimals(0.01), 2);
+        assert_eq!(decimals(0.00001), 5);
+        assert_eq!(decimals(1.0), 0);
+        assert_eq!(format!("{:.5}", snap(0.1, 0.00001, false)), "0.10000");
+        assert_eq!(format!("{:.5}", snap(0.123_456, 0.00001, false)), "0.12345");
+        assert_eq!(format!("{:.2}", snap(49999.996, 0.01, false)), "49999.99");
+        assert_eq!(format!("{:.2}", snap(50000.004, 0.01, true)), "50000.01");
+        assert_eq!(format!("{:.2}", snap(50100.0, 0.01, true)), "50100.00");
+        assert_eq!(format!("{:.2}", snap(50000.01, 0.01, true)), "50000.01");
+
+        // Buys round the price down, sells round it up; response price is data[3]
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+        let buy = Tensor::new(vec![4], vec![1.0, 0.123456, 49950.006, 0.0], 1.0);
+        let response = resolver.resolve("binance:order:BTCUSDT", vec![&buy]).unwrap();
+        assert!((response.data[3] - 49950.0).abs() < 0.005);
+
+        let sell = Tensor::new(vec![4], vec![-1.0, 0.123456, 50050.004, 0.0], 1.0);
+        let unsided = Tensor::new(vec![4], vec![0.0, 0.123456, 50050.004, 0.0], 1.0);
+        assert!(resolver.resolve("binance:order:BTCUSDT", vec![&unsided]).is_err());
+        let response = resolver.resolve("binance:order:BTCUSDT", vec![&sell]).unwrap();
+        assert!((response.data[3] - 50050.01).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_orderbook_depth_is_capped() {
+        let resolver = BinanceResolver::new();
+        let depth = |limit: u32| {
+            let uri = format!("binance:orderbook:symbol=BTCUSDT,limit={}", limit);
+            resolver.resolve(&uri, vec![]).unwrap().shape[0] / 2
+        };
+
+        assert_eq!(MAX_ORDERBOOK_DEPTH, 1000);
+        assert_eq!(depth(999), 999);
+        assert_eq!(depth(1000), 1000);
+        assert_eq!(depth(1001), 1000);
+        assert_eq!(depth(10_000), 1000);
+    }
+
+    #[test]
+    fn test_base_url_override() {
+        let resolver = BinanceResolver::new()
+            .with_base_url("https://api1.binance.com/")
+            .with_futures_base_url("https://fapi.example.com");
+
+        assert_eq!(resolver.base_url, "https://api1.binance.com");
+        assert_eq!(resolver.futures_base_url, "https://fapi.example.com");
+    }
+
+    #[test]
+    fn test_listed_endpoints_are_served() {
+        let resolver = BinanceResolver::new();
+        for endpoint in ENDPOINTS {
+            let result = resolver.resolve(&format!("binance:{}:BTCUSDT", endpoint), vec![]);
+            if let Err(e) = result {
+                assert!(!e.starts_with("Unknown Binance endpoint"), "{}", e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_margin_endpoints() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+
+        let account = resolver.resolve("binance:margin_account", vec![]).unwrap();
+        assert_eq!(account.shape, vec![4]);
+        assert_eq!(account.data[2], account.data[0] / account.data[1]);
+        assert_eq!(account.data[3], account.data[2] - MARGIN_LIQUIDATION_LEVEL);
+
+        let balance = resolver.resolve("binance:margin_balance:asset=BTC", vec![]).unwrap();
+        assert_eq!(balance.shape, vec![4]); // free, locked, borrowed, interest
+
+        // Borrow and repay are refused rather than simulated
+        let borrow = resolver
+            .resolve("binance:margin_borrow:asset=USDT,amount=100", vec![])
+            .unwrap_err();
+        assert!(borrow.contains("not implemented"), "{}", borrow);
+
+        // Graph-computed amount from the first input
+        let amount = Tensor::scalar(0.25, 1.0);
+        let repay = resolver
+            .resolve("binance:margin_repay:asset=BTC", vec![&amount])
+            .unwrap_err();
+        assert!(repay.contains("repay of 0.25 BTC"), "{}", repay);
+
+        assert!(resolver.resolve("binance:margin_repay:asset=BTC", vec![]).is_err());
+        assert!(resolver
+            .resolve("binance:margin_borrow:asset=BTC,amount=-1", vec![])
+            .is_err());
+        assert!(BinanceResolver::new()
+            .resolve("binance:margin_account", vec![])
+            .is_err());
+    }
+
+    #[test]
+    fn test_estimate_fill_price() {
+        let resolver = BinanceResolver::new();
+
+        // First ask level is 0.1 @ 50010, second is 0.11 @ 50020
+        assert_eq!(resolver.estimate_fill_price("BTCUSDT", "BUY", 0.1).unwrap(), 50010.0);
+        let two_levels = resolver.estimate_fill_price("BTCUSDT", "BUY", 0.2).unwrap();
+        assert!((two_levels - 50015.0).abs() < 0.01);
+
+        let sell = resolver.estimate_fill_price("BTCUSDT", "SELL", 0.1).unwrap();
+        assert_eq!(sell, 49990.0);
+
+        assert!(resolver.estimate_fill_price("BTCUSDT", "BUY", 1_000.0).is_err());
+        assert!(resolver.estimate_fill_price("BTCUSDT", "BUY", 0.0).is_err());
+    }
+
+    #[test]
+    fn test_normalize_pair() {
+        assert_eq!(normalize_pair("BTC/USDT").unwrap(), "BTCUSDT");
+        assert_eq!(normalize_pair("btc-usdt").unwrap(), "BTCUSDT");
+        assert_eq!(normalize_pair("eth_btc").unwrap(), "ETHBTC");
+        assert_eq!(normalize_pair("BTCUSDT").unwrap(), "BTCUSDT");
+        assert_eq!(normalize_pair("btc/pln").unwrap(), "BTCPLN");
+
+        assert!(normalize_pair("/USDT").is_err());
+        assert!(normalize_pair("BTC/USDT/ETH").is_err());
+        assert!(normalize_pair("BTC USDT").is_err());
+    }
+
+    #[test]
+    fn test_market_data_served_for_any_symbol() {
+        let resolver = BinanceResolver::new();
+        assert!(resolver.resolve("binance:ticker:btc/usdt", vec![]).is_ok());
+        assert!(resolver.resolve("binance:ticker:AVAX/USDT", vec![]).is_ok());
+        assert!(resolver.resolve("binance:orderbook:symbol=AVAXUSDT,limit=5", vec![]).is_ok());
+        assert!(resolver.resolve("binance:ticker:BTC USDT", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_orders_need_exchange_info() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+        assert!(resolver.get_exchange_info("LTCBTC").is_ok());
+
+        let err = resolver.get_exchange_info("AVAXUSDT").unwrap_err();
+        assert!(err.contains("No exchange info"), "{}", err);
+        let order = Tensor::new(vec![4], vec![1.0, 1.0, 30.0, 0.0], 1.0);
+        assert!(resolver.resolve("binance:order:AVAXUSDT", vec![&order]).is_err());
+        assert!(resolver.resolve("binance:exchange_info:AVAXUSDT", vec![]).is_err());
+    }
+
+    #[test]
+    fn test_with_environment() {
+        let resolver = BinanceResolver::new().with_environment(Environment::Testnet);
+        assert_eq!(resolver.environment, Environment::Testnet);
+        assert_eq!(resolver.base_url, "https://testnet.binance.vision");
+
+        let resolver = resolver.with_environment(Environment::Mainnet);
+        assert_eq!(resolver.base_url, "https://api.binance.com");
+        assert_eq!(resolver.futures_base_url, "https://fapi.binance.com");
+
+        // Explicit overrides survive a later environment switch
+        let resolver = BinanceResolver::new()
+            .with_base_url("https://api1.binance.com/")
+            .with_environment(Environment::Testnet);
+        assert_eq!(resolver.base_url, "https://api1.binance.com");
+        assert_eq!(resolver.futures_base_url, "https://testnet.binancefuture.com");
+    }
+
+    #[test]
+    fn test_max_slippage_gate() {
+        // Buying 0.2 fills at ~50015 against a 50000 mid: ~3 bps
+        let market_buy = Tensor::new(vec![4], vec![1.0, 0.2, 0.0, 0.0], 1.0);
+
+        let strict = BinanceResolver::with_credentials("key", "secret").with_max_slippage_bps(1.0);
+        assert!(strict.resolve("binance:order:BTCUSDT", vec![&market_buy]).is_err());
+
+        let loose = BinanceResolver::with_credentials("key", "secret").with_max_slippage_bps(5.0);
+        assert!(loose.resolve("binance:order:BTCUSDT", vec![&market_buy]).is_ok());
+
+        let ungated = BinanceResolver::with_credentials("key", "secret");
+        assert!(ungated.resolve("binance:order:BTCUSDT", vec![&market_buy]).is_ok());
+    }
+
+    #[test]
+    fn test_maker_only_reprices_crossing_orders() {
+        let resolver = BinanceResolver::with_credentials("key", "secret").with_maker_only();
+
+        // Best bid 49990, best ask 50010, tick 0.01; response price is data[3]
+        let crossing_buy = Tensor::new(vec![4], vec![1.0, 0.1, 50020.0, 0.0], 1.0);
+        let response = resolver.resolve("binance:order:BTCUSDT", vec![&crossing_buy]).unwrap();
+        assert!(response.data[3] < 50010.0 && response.data[3] > 50009.9);
+
+        let crossing_sell = Tensor::new(vec![4], vec![-1.0, 0.1, 49980.0, 0.0], 1.0);
+        let response = resolver.resolve("binance:order:BTCUSDT", vec![&crossing_sell]).unwrap();
+        assert!(response.data[3] > 49990.0 && response.data[3] < 49990.1);
+
+        let resting_buy = Tensor::new(vec![4], vec![1.0, 0.1, 49950.0, 0.0], 1.0);
+        let response = resolver.resolve("binance:order:BTCUSDT", vec![&resting_buy]).unwrap();
+        assert_eq!(response.data[3], 49950.0);
+    }
+
+    #[test]
+    fn test_exchange_info_limits() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+
+        let info = resolver.get_exchange_info("BTCUSDT").unwrap();
+        assert_eq!(resolver.get_exchange_info("BTCUSDT").unwrap(), info);
+
+        // Filters are per symbol: a BTC-quoted pair has BTC-sized limits
+        let eth_btc = resolver.get_exchange_info("ETHBTC").unwrap();
+        assert_eq!(eth_btc.tick_size, 0.00001);
+        assert_eq!(eth_btc.step_size, 0.0001);
+        assert_eq!(eth_btc.min_notional, 0.0001);
+        assert_ne!(eth_btc.tick_size, info.tick_size);
+
+        // 0.00005 BTC at 49000 is below the 5.0 minimum notional
+        let dust = Tensor::new(vec![4], vec![1.0, 0.00005, 49000.0, 0.0], 1.0);
+        let err = resolver.resolve("binance:order:BTCUSDT", vec![&dust]).unwrap_err();
+        assert!(err.contains("notional"));
+
+        let tiny = Tensor::new(vec![4], vec![1.0, 0.000001, 0.0, 0.0], 1.0);
+        let err = resolver.resolve("binance:order:BTCUSDT", vec![&tiny]).unwrap_err();
+        assert!(err.contains("below minimum"));
+    }
+
+    #[test]
+    fn test_form_encode() {
+        assert_eq!(form_encode("x-AB_c.d~e"), "x-AB_c.d~e");
+        assert_eq!(form_encode("a/b:c d+e"), "a%2Fb%3Ac%20d%2Be");
+        assert_eq!(form_encode("%&="), "%25%26%3D");
+    }
+
+    #[test]
+    fn test_signed_body_matches_binance_example() {
+        let resolver = BinanceResolver::with_credentials(
+            "vmPUZE6mv9SD5VNHk4HlWFsOr6aKE2zvsw0MuIgwCIPy6utIco14y7Ju91duEh8A",
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j",
+        );
+        let params = [
+            ("symbol", "LTCBTC".to_string()),
+            ("side", "BUY".to_string()),
+            ("type", "LIMIT".to_string()),
+            ("timeInForce", "GTC".to_string()),
+            ("quantity", "1".to_string()),
+            ("price", "0.1".to_string()),
+            ("recvWindow", "5000".to_string()),
+            ("timestamp", "1499827319559".to_string()),
+        ];
+
+        let body = resolver.signed_body(&params).unwrap();
+        assert!(body.ends_with(
+            "&signature=c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        ));
+    }
+
+    #[test]
+    fn test_signed_body_encodes_client_order_id() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+        let params = [
+            ("symbol", "BTCUSDT".to_string()),
+            ("newClientOrderId", "mm/1:a b".to_string()),
+        ];
+
+        let body = resolver.signed_body(&params).unwrap();
+        let (payload, signature) = body.split_once("&signature=").unwrap();
+        assert_eq!(payload, "symbol=BTCUSDT&newClientOrderId=mm%2F1%3Aa%20b");
+
+        // Signature is over the encoded payload, not the raw values
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+        mac.update(payload.as_bytes());
+        assert_eq!(signature, hex::encode(mac.finalize().into_bytes()));
+
+        assert!(BinanceResolver::new().signed_body(&params).is_err());
+    }
+
+    #[test]
+    fn test_test_orders_run_the_same_checks() {
+        // No credentials needed; the order is validated but not sent
+        let resolver = BinanceResolver::new().with_max_slippage_bps(1.0);
+        let near = Tensor::new(vec![4], vec![1.0, 0.1, 49950.006, 0.0], 1.0);
+        let response = resolver.resolve("binance:order:symbol=BTCUSDT,test=true", vec![&near]).unwrap();
+        assert_eq!(response.data[0], 0.0);
+        assert!((response.data[3] - 49950.0).abs() < 0.005);
+
+        let far = Tensor::new(vec![4], vec![1.0, 0.1, 9000.0, 0.0], 1.0);
+        let market_buy = Tensor::new(vec![4], vec![1.0, 0.2, 0.0, 0.0], 1.0);
+        let dust = Tensor::new(vec![4], vec![1.0, 0.00005, 49000.0, 0.0], 1.0);
+        for rejected in [&far, &market_buy, &dust] {
+            assert!(resolver
+                .resolve("binance:order:symbol=BTCUSDT,test=true", vec![rejected])
+                .is_err());
+        }
+        assert!(resolver.resolve("binance:order:symbol=AVAXUSDT,test=true", vec![&near]).is_err());
+        assert!(resolver.resolve("binance:order:symbol=BTCUSDT", vec![&near]).is_err());
+
+        let prices = Tensor::new(vec![2], vec![49900.0, 9000.0], 1.0);
+        let sides = Tensor::new(vec![2], vec![1.0, 1.0], 1.0);
+        let size = Tensor::scalar(0.1, 1.0);
+        let response = resolver
+            .resolve("binance:batch_order:symbol=BTCUSDT,test=true", vec![&prices, &sides, &size])
+            .unwrap();
+        assert_eq!(response.data[1], 1.0);
+        assert_eq!(response.data[4 + 1], ORDER_STATUS_REJECTED);
+    }
+
+    #[test]
+    fn test_order_with_client_order_id() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+        let order = Tensor::new(vec![4], vec![1.0, 0.1, 50000.0, 0.0], 1.0);
+
+        let response = resolver
+            .resolve("binance:order:symbol=BTCUSDT,client_order_id=mm/1:a", vec![&order])
+            .unwrap();
+        assert_eq!(response.data[3], 50000.0);
+    }
+
+    #[test]
+    fn test_order_graph_form() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+
+        // As in market_making.0: side in the URI, inputs [price, size]
+        let price = Tensor::scalar(49875.0, 0.5);
+        let size = Tensor::scalar(0.01, 1.0);
+        let response = resolver
+            .resolve("binance:order:symbol=BTCUSDT,side=BUY", vec![&price, &size])
+            .unwrap();
+        assert_eq!(response.data[3], 49875.0);
+
+        assert!(resolver
+            .resolve("binance:order:symbol=BTCUSDT,side=HOLD", vec![&price, &size])
+            .is_err());
+        assert!(resolver
+            .resolve("binance:order:symbol=BTCUSDT", vec![&price, &size])
+            .is_err());
+    }
+
+    #[test]
+    fn test_klines_oldest_first() {
+        let resolver = BinanceResolver::new();
+
+        let candles = resolver
+            .resolve("binance:klines:symbol=BTCUSDT,interval=1h,limit=3", vec![])
+            .unwrap();
+        assert_eq!(candles.shape, vec![3, 5]);
+        let closes: Vec<f32> = candles.data.chunks(5).map(|row| row[3]).collect();
+        assert_eq!(closes, vec![49980.0, 49990.0, 50000.0]);
+
+        let capped = resolver.resolve("binance:klines:symbol=BTCUSDT,limit=5000", vec![]).unwrap();
+        assert_eq!(capped.shape, vec![MAX_KLINES, 5]);
+
+        assert!(resolver.resolve("binance:klines:symbol=BTCUSDT,interval=2h", vec![]).is_err());
+        assert_eq!(interval_param(Interval::D1), "1d");
+    }
+
+    #[test]
+    fn test_funding_rate() {
+        let resolver = BinanceResolver::new();
+
+        let funding = resolver.resolve("binance:funding:BTCUSDT", vec![]).unwrap();
+        assert_eq!(funding.shape, vec![3]);
+        assert!(funding.data[1] > 0.0 && funding.data[1] <= FUNDING_INTERVAL_SECS as f32);
+        assert_eq!(funding.data[2], 50005.0);
+
+        // 2024-02-01 07:00:00 UTC is one hour before the 08:00 funding
+        assert_eq!(seconds_to_next_funding(1_706_770_800), 3600);
+        assert_eq!(seconds_to_next_funding(1_706_774_400), FUNDING_INTERVAL_SECS);
+    }
+
+    #[test]
+    fn test_batch_order_matches_grid_graph() {
+        let resolver = BinanceResolver::with_credentials("key", "secret");
+
+        // Same inputs as place_grid_orders: [grid_prices, grid_sides, config_order_size]
+        let prices = Tensor::new(vec![3], vec![49900.0, 0.0, 50100.0], 1.0);
+        let sides = Tensor::new(vec![3], vec![1.0, 1.0, -1.0], 1.0);
+        let size = Tensor::scalar(0.1, 1.0);
+
+        let response = resolver
+            .resolve("binance:batch_order:symbol=BTCUSDT", vec![&prices, &sides, &size])
+            .unwrap();
+        assert_eq!(response.shape, vec![3, 4]);
+
+        // The zero price is below the minimum notional; its neighbours still go through
+        let rows: Vec<&[f32]> = response.data.chunks(4).collect();
+        assert_eq!(rows[0][3], 49900.0);
+        assert_eq!(rows[1][1], ORDER_STATUS_REJECTED);
+        assert_eq!(rows[2][3], 50100.0);
+        let err = resolver.validate_order("BTCUSDT", "BUY", 0.1, Some(0.0)).unwrap_err();
+        assert!(err.contains("notional"), "{}", err);
+
+        // A zero side is rejected rather than read as either side
+        let unsided = Tensor::new(vec![3], vec![1.0, 0.0, -1.0], 1.0);
+        let response = resolver
+            .resolve("binance:batch_order:symbol=BTCUSDT", vec![&prices, &unsided, &size])
+            .unwrap();
+        assert_eq!(response.data[4 + 1], ORDER_STATUS_REJECTED);
+
+        let ids = "binance:batch_order:symbol=BTCUSDT,client_order_ids=a1.a2.a3";
+        assert!(resolver.resolve(ids, vec![&prices, &sides, &size]).is_ok());
+        let too_few = "binance:batch_order:symbol=BTCUSDT,client_order_ids=a1.a2";
+        assert!(resolver.resolve(too_few, vec![&prices, &sides, &size]).is_err());
+
+        let sizes = Tensor::new(vec![2], vec![0.1, 0.1], 1.0);
+        assert!(resolver
+            .resolve("binance:batch_order:BTCUSDT", vec![&prices, &sides, &sizes])
+            .is_err());
+        assert!(resolver.resolve("binance:batch_order:BTCUSDT", vec![&prices]).is_err());
+        assert!(BinanceResolver::new()
+            .resolve("binance:batch_order:BTCUSDT", vec![&prices, &sides, &size])
+            .is_err());
+    }
 }
@@ -4,14 +4,23 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use zerolang::{ExternalResolver, Tensor};
 
+use crate::resolvers::Environment;
+
 /// HTTP resolver for external API calls
 pub struct HttpResolver {
     /// HTTP client
     client: reqwest::Client,
     /// Base URLs for different services
     base_urls: HashMap<String, String>,
+    /// Proxy all requests are routed through
+    proxy_url: Option<String>,
+    /// Per-request timeout
+    timeout: Option<Duration>,
+    /// Trading environment (selects hosts and OKX's simulated-trading header)
+    environment: Environment,
 }
 
 impl HttpResolver {
@@ -27,6 +36,9 @@ impl HttpResolver {
         Self {
             client: reqwest::Client::new(),
             base_urls,
+            proxy_url: None,
+            timeout: None,
+            environment: Environment::Mainnet,
         }
     }
 
@@ -36,6 +48,77 @@ impl HttpResolver {
         self
     }
 
+    /// Point the default exchange services at their mainnet or testnet hosts
+    ///
+    /// OKX serves demo trading from its production host, so its base URL is
+    /// unchanged; requests to it carry `x-simulated-trading: 1` instead (see
+    /// `headers`). OKX has no separate testnet, so Testnet uses demo trading.
+    pub fn with_environment(mut self, environment: Environment) -> Self {
+        self.environment = environment;
+        let (binance, hyperliquid) = match environment {
+            Environment::Mainnet => ("https://api.binance.com", "https://api.hyperliquid.xyz"),
+            Environment::Testnet => (
+                "https://testnet.binance.vision",
+                "https://api.hyperliquid-testnet.xyz",
+            ),
+            Environment::Demo => (
+                "https://demo-api.binance.com",
+                "https://api.hyperliquid-testnet.xyz",
+            ),
+        };
+
+        self.with_base_url("binance", binance)
+            .with_base_url("hyperliquid", hyperliquid)
+    }
+
+    /// Extra headers sent with every request to a service
+    pub fn headers(&self, service: &str) -> Vec<(&'static str, &'static str)> {
+        match (service, self.environment) {
+            ("okx", Environment::Testnet | Environment::Demo) => {
+                vec![("x-simulated-trading", "1")]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Names of the configured services, sorted
+    pub fn services(&self) -> Vec<&str> {
+        let mut services: Vec<&str> = self.base_urls.keys().map(|s| s.as_str()).collect();
+        services.sort_unstable();
+        services
+    }
+
+    /// Route all requests through a proxy (e.g. "http://proxy.internal:3128")
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, String> {
+        self.proxy_url = Some(proxy_url.to_string());
+        self.rebuild_client()
+    }
+
+    /// Fail requests that take longer than `timeout`
+    pub fn with_timeout(mut self, timeout: Duration) -> Result<Self, String> {
+        self.timeout = Some(timeout);
+        self.rebuild_client()
+    }
+
+    /// Rebuild the HTTP client from the proxy and timeout settings
+    fn rebuild_client(mut self) -> Result<Self, String> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy_url) = &self.proxy_url {
+            let proxy = reqwest::Proxy::all(proxy_url)
+                .map_err(|e| format!("Invalid proxy URL {}: {}", proxy_url, e))?;
+            builder = builder.proxy(proxy);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        self.client = builder
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        Ok(self)
+    }
+
     /// Parse URI and extract method, service, and path
     /// URI format: "http:{method}:{service}:{path}"
     /// Example: "http:get:binance:/api/v3/ticker/price?symbol=BTCUSDT"
@@ -80,15 +163,17 @@ impl ExternalResolver for HttpResolver {
     fn resolve(&self, uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
         let (method, service, path) = self.parse_uri(uri)?;
         let url = self.build_url(&service, &path)?;
+        let headers = self.headers(&service);
 
         // For now, return a placeholder tensor
         // TODO: Implement actual HTTP calls with tokio runtime
         
         tracing::info!(
-            "HTTP {} {} (inputs: {})",
+            "HTTP {} {} (inputs: {}, headers: {:?})",
             method.to_uppercase(),
             url,
-            inputs.len()
+            inputs.len(),
+            headers
         );
 
         // Return a placeholder tensor indicating the request was parsed
@@ -135,4 +220,38 @@ mod tests {
         let result = resolver.parse_uri("invalid");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_with_proxy() {
+        assert!(HttpResolver::new().with_proxy("http://127.0.0.1:3128").is_ok());
+        assert!(HttpResolver::new().with_proxy("http://[::1").is_err());
+    }
+
+    #[test]
+    fn test_with_timeout_keeps_proxy() {
+        let resolver = HttpResolver::new()
+            .with_proxy("http://127.0.0.1:3128")
+            .and_then(|r| r.with_timeout(Duration::from_secs(5)))
+            .unwrap();
+
+        assert_eq!(resolver.proxy_url.as_deref(), Some("http://127.0.0.1:3128"));
+        assert_eq!(resolver.timeout, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_with_environment() {
+        let resolver = HttpResolver::new().with_environment(Environment::Testnet);
+
+        let url = resolver.build_url("hyperliquid", "/info").unwrap();
+        assert_eq!(url, "https://api.hyperliquid-testnet.xyz/info");
+        let url = resolver.build_url("okx", "/api/v5/public/time").unwrap();
+        assert_eq!(url, "https://www.okx.com/api/v5/public/time");
+
+        // OKX demo trading is selected per request
+        assert_eq!(resolver.headers("okx"), vec![("x-simulated-trading", "1")]);
+        assert!(resolver.headers("hyperliquid").is_empty());
+        let demo = HttpResolver::new().with_environment(Environment::Demo);
+        assert_eq!(demo.headers("okx"), vec![("x-simulated-trading", "1")]);
+        assert!(HttpResolver::new().headers("okx").is_empty());
+    }
 }
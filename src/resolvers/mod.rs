@@ -2,9 +2,191 @@
 //!
 //! These resolvers bridge 0-lang graphs to external services.
 
+pub mod cache;
+pub mod config;
 pub mod exchange;
 pub mod http;
 
+use serde::{Deserialize, Serialize};
+
 // Re-export resolver types
+pub use cache::{CachingResolver, ResolverCache};
+pub use config::ConnectorConfig;
 pub use exchange::binance::BinanceResolver;
 pub use http::HttpResolver;
+
+/// Trading environment a connector talks to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Environment {
+    /// Production endpoints with real funds
+    #[default]
+    Mainnet,
+    /// Exchange testnet endpoints
+    Testnet,
+    /// Exchange demo-trading endpoints (falls back to testnet where absent)
+    Demo,
+}
+
+impl std::str::FromStr for Environment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" => Ok(Environment::Mainnet),
+            "testnet" => Ok(Environment::Testnet),
+            "demo" => Ok(Environment::Demo),
+            _ => Err(format!("Unknown environment: {}", s)),
+        }
+    }
+}
+
+/// Candle interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Interval {
+    M1,
+    M5,
+    M15,
+    H1,
+    H4,
+    D1,
+}
+
+impl std::str::FromStr for Interval {
+    type Err = String;
+
+    /// Parse "1m", "5m", "15m", "1h", "4h" or "1d" (case-sensitive: "1M" is a month on most venues)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(Interval::M1),
+            "5m" => Ok(Interval::M5),
+            "15m" => Ok(Interval::M15),
+            "1h" => Ok(Interval::H1),
+            "4h" => Ok(Interval::H4),
+            "1d" => Ok(Interval::D1),
+            _ => Err(format!("Unknown interval: {}", s)),
+        }
+    }
+}
+
+/// Exchange type of a connector
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ExchangeType {
+    #[serde(rename = "CEX")]
+    Cex,
+    #[serde(rename = "DEX")]
+    Dex,
+}
+
+impl std::fmt::Display for ExchangeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExchangeType::Cex => f.pad("CEX"),
+            ExchangeType::Dex => f.pad("DEX"),
+        }
+    }
+}
+
+/// How far a connector's resolver is implemented
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectorStatus {
+    /// Dedicated resolver returning simulated data
+    Simulated,
+    /// Raw HTTP access through HttpResolver only
+    RawHttp,
+}
+
+/// A connector the runtime can resolve URIs for
+#[derive(Debug, Clone, Serialize)]
+pub struct ConnectorInfo {
+    /// Connector name (the service in "http:{method}:{service}:{path}")
+    pub name: String,
+    /// Centralized or decentralized exchange
+    #[serde(rename = "type")]
+    pub exchange_type: ExchangeType,
+    /// Chain a DEX settles on
+    pub chain: Option<&'static str>,
+    /// Implementation status
+    pub status: ConnectorStatus,
+    /// Rust module implementing the resolver
+    pub module: &'static str,
+    /// URI scheme of the resolver serving this connector
+    pub resolver: &'static str,
+    /// Endpoints of the dedicated resolver (empty for raw HTTP access)
+    pub endpoints: Vec<&'static str>,
+}
+
+/// Connectors registered with the resolvers, sorted by name
+pub fn registered_connectors() -> Vec<ConnectorInfo> {
+    HttpResolver::new()
+        .services()
+        .into_iter()
+        .map(|name| {
+            let (resolver, module, status, endpoints) = match name {
+                "binance" => (
+                    "binance",
+                    "resolvers::exchange::binance",
+                    ConnectorStatus::Simulated,
+                    exchange::binance::ENDPOINTS.to_vec(),
+                ),
+                _ => ("http", "resolvers::http", ConnectorStatus::RawHttp, Vec::new()),
+            };
+            let (exchange_type, chain) = match name {
+                "hyperliquid" => (ExchangeType::Dex, Some("hyperliquid")),
+                _ => (ExchangeType::Cex, None),
+            };
+
+            ConnectorInfo {
+                name: name.to_string(),
+                exchange_type,
+                chain,
+                status,
+                module,
+                resolver,
+                endpoints,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_from_str() {
+        assert_eq!("mainnet".parse::<Environment>(), Ok(Environment::Mainnet));
+        assert_eq!("Testnet".parse::<Environment>(), Ok(Environment::Testnet));
+        assert_eq!("demo".parse::<Environment>(), Ok(Environment::Demo));
+        assert!("staging".parse::<Environment>().is_err());
+    }
+
+    #[test]
+    fn test_interval_from_str() {
+        assert_eq!("15m".parse::<Interval>(), Ok(Interval::M15));
+        assert_eq!("4h".parse::<Interval>(), Ok(Interval::H4));
+        assert!("1M".parse::<Interval>().is_err());
+        assert!("2h".parse::<Interval>().is_err());
+    }
+
+    #[test]
+    fn test_registered_connectors() {
+        let connectors = registered_connectors();
+
+        let binance = connectors.iter().find(|c| c.name == "binance").unwrap();
+        assert_eq!(binance.resolver, "binance");
+        assert!(binance.endpoints.contains(&"ticker"));
+
+        let hyperliquid = connectors.iter().find(|c| c.name == "hyperliquid").unwrap();
+        assert_eq!(hyperliquid.exchange_type, ExchangeType::Dex);
+        assert_eq!(hyperliquid.resolver, "http");
+        assert_eq!(hyperliquid.chain, Some("hyperliquid"));
+        assert_eq!(hyperliquid.status, ConnectorStatus::RawHttp);
+
+        let json = serde_json::to_value(binance).unwrap();
+        assert_eq!(json["type"], "CEX");
+        assert_eq!(json["status"], "simulated");
+        assert_eq!(json["module"], "resolvers::exchange::binance");
+    }
+}
@@ -0,0 +1,226 @@
+//! Retry with exponential backoff
+//!
+//! Shared retry policy for resolver calls and the execution loop.
+
+use std::collections::hash_map::RandomState;
+use std::fmt::Display;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// Backoff configuration
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Delay before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Growth factor applied per attempt
+    pub multiplier: f64,
+    /// Jitter as a fraction of the delay (0.0 = none, 0.5 = ±50%)
+    pub jitter: f64,
+    /// Maximum number of attempts, including the first call
+    pub max_attempts: u32,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_attempts: 5,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Set the base delay
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the maximum delay
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set the backoff multiplier
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the jitter fraction (clamped to 0.0..=1.0)
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the maximum number of attempts
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Delay before retry number `retry` (0-based), without jitter
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self.multiplier.max(1.0).powi(retry as i32);
+        let delay = self.base_delay.as_secs_f64() * factor;
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// Delay before retry number `retry` with jitter applied
+    fn jittered_delay_for(&self, retry: u32) -> Duration {
+        let delay = self.delay_for(retry).as_secs_f64();
+        if self.jitter <= 0.0 {
+            return Duration::from_secs_f64(delay);
+        }
+
+        // Uniform in [-1.0, 1.0]
+        let unit = random_u64() as f64 / u64::MAX as f64 * 2.0 - 1.0;
+        let jittered = delay * (1.0 + self.jitter * unit);
+        Duration::from_secs_f64(jittered.clamp(0.0, self.max_delay.as_secs_f64()))
+    }
+}
+
+/// Run `operation` until it succeeds, fails with a non-retryable error,
+/// or `max_attempts` is exhausted. The last error is returned on failure.
+pub async fn retry_with_backoff<T, E, F, Fut, P>(
+    config: &RetryConfig,
+    mut operation: F,
+    is_retryable: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: Fn(&E) -> bool,
+    E: Display,
+{
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < config.max_attempts && is_retryable(&e) => {
+                let delay = config.jittered_delay_for(attempt - 1);
+                tracing::warn!(
+                    "Attempt {}/{} failed: {} (retrying in {:?})",
+                    attempt,
+                    config.max_attempts,
+                    e,
+                    delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Cheap random value from the std hasher's per-instance random keys
+fn random_u64() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_config() -> RetryConfig {
+        RetryConfig::default()
+            .with_base_delay(Duration::from_millis(1))
+            .with_max_delay(Duration::from_millis(5))
+            .with_jitter(0.0)
+    }
+
+    #[test]
+    fn test_delay_grows_and_caps() {
+        let config = RetryConfig::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500))
+            .with_multiplier(2.0);
+
+        assert_eq!(config.delay_for(0), Duration::from_millis(100));
+        assert_eq!(config.delay_for(1), Duration::from_millis(200));
+        assert_eq!(config.delay_for(2), Duration::from_millis(400));
+        assert_eq!(config.delay_for(3), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_jitter_stays_in_bounds() {
+        let config = RetryConfig::default()
+            .with_base_delay(Duration::from_millis(100))
+            .with_jitter(0.5);
+
+        for _ in 0..100 {
+            let delay = config.jittered_delay_for(0);
+            assert!(delay >= Duration::from_millis(50));
+            assert!(delay <= Duration::from_millis(150));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_until_success() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<u32, String> = retry_with_backoff(
+            &fast_config(),
+            || async {
+                let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                if n < 3 {
+                    Err("timeout".to_string())
+                } else {
+                    Ok(n)
+                }
+            },
+            |_| true,
+        )
+        .await;
+
+        assert_eq!(result, Ok(3));
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_fails_immediately() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), String> = retry_with_backoff(
+            &fast_config(),
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("invalid api key".to_string())
+            },
+            |e| !e.contains("api key"),
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), String> = retry_with_backoff(
+            &fast_config().with_max_attempts(3),
+            || async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err("rate limited".to_string())
+            },
+            |_| true,
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,160 +1,1013 @@
 //! Runtime for executing 0-hummingbot strategies
 //!
 //! Handles the execution loop, market data, and order management.
+//!
+//! The bundled .0 files hold the graph's debug representation, which the
+//! 0-VM can't load, so the runtime evaluates them through `interpreter`. Each
+//! cycle fetches market data once and serves it to the graph's market data
+//! nodes; the graph's order nodes go through the runtime's order gate.
 
+use std::collections::HashMap;
+use std::fs::File;
+use std::future::Future;
+use std::io::BufReader;
 use std::path::Path;
-use std::sync::Arc;
-use zerolang::{ExternalResolver, RuntimeGraph, Tensor, VM};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use zerolang::{ExternalResolver, Tensor};
+
+use crate::client_id::ClientOrderIdGenerator;
+use crate::graph::{GraphFile, GraphSummary, NodeType};
+use crate::interpreter;
+use crate::resolvers::exchange::binance::{normalize_pair, ORDER_STATUS_REJECTED};
+use crate::resolvers::{CachingResolver, ConnectorConfig, HttpResolver, ResolverCache};
+use crate::retry::{retry_with_backoff, RetryConfig};
+use crate::strategies::PARAM_PREFIX;
 
-use crate::resolvers::HttpResolver;
+/// Default minimum confidence of an order's inputs, the bundled strategies'
+/// `config_confidence_threshold`. Placeholder market data reports 0.5, so
+/// orders built on it are skipped unless the threshold is lowered.
+pub const DEFAULT_MIN_CONFIDENCE: f32 = 0.8;
+
+/// Order status reported for an order the confidence gate skipped
+/// (1.0 = open, `ORDER_STATUS_REJECTED` = rejected)
+pub const ORDER_STATUS_SKIPPED: f32 = 0.0;
 
 /// Trading runtime configuration
 pub struct RuntimeConfig {
     /// Strategy graph path
     pub strategy_path: String,
-    /// Connector name
-    pub connector: String,
+    /// Connector to trade on
+    pub connector: ConnectorConfig,
     /// Trading pair
     pub pair: String,
     /// Execution interval in milliseconds
     pub interval_ms: u64,
     /// Paper trading mode
     pub paper_mode: bool,
+    /// Orders whose inputs carry less confidence than this are skipped
+    pub min_confidence: f32,
+    /// Strategy parameters, written into the graph's `config_*` constants
+    pub params: HashMap<String, f64>,
+    /// File to record every External result the graph sees to (a
+    /// `ResolverCache` recording, for replay). Truncated at start and
+    /// appended to after every cycle.
+    pub record_path: Option<String>,
+}
+
+/// Market data fetched at the start of each cycle
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketSnapshot {
+    /// Last traded price
+    pub last_price: f32,
+    /// Best bid price
+    pub best_bid: f32,
+    /// Best ask price
+    pub best_ask: f32,
+    /// Lowest confidence of the fetched data
+    pub confidence: f32,
+}
+
+impl MarketSnapshot {
+    /// Build from a ticker tensor and an orderbook tensor (bids then asks)
+    pub fn from_tensors(ticker: &Tensor, orderbook: &Tensor) -> Result<Self, String> {
+        let levels = orderbook.data.len() / 4;
+        if ticker.data.is_empty() || levels == 0 {
+            return Err("Empty market data".to_string());
+        }
+
+        Ok(Self {
+            last_price: ticker.data[0],
+            best_bid: orderbook.data[0],
+            best_ask: orderbook.data[levels * 2],
+            confidence: ticker.confidence.min(orderbook.confidence),
+        })
+    }
+}
+
+/// Orders handled in one cycle
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CycleReport {
+    /// Orders sent to the venue (simulated in paper mode)
+    pub submitted: usize,
+    /// Orders skipped for low input confidence
+    pub skipped: usize,
+    /// Orders the connector rejected
+    pub rejected: usize,
+    /// Why the graph stopped evaluating, if it did. Orders placed before
+    /// the failure are still counted above.
+    pub error: Option<String>,
+}
+
+/// Value of `key` in a "{scheme}:{endpoint}:key=value,..." URI
+fn uri_param<'a>(uri: &'a str, key: &str) -> Option<&'a str> {
+    uri.splitn(3, ':')
+        .nth(2)?
+        .split(',')
+        .find_map(|pair| pair.strip_prefix(key)?.strip_prefix('='))
+}
+
+/// Append a `key=value` param to a "{scheme}:{endpoint}:{params}" URI
+fn with_param(uri: &str, param: &str) -> String {
+    match uri.splitn(3, ':').nth(2) {
+        Some(params) if params.contains('=') => format!("{},{}", uri, param),
+        // Bare form "binance:order:BTCUSDT" names the symbol
+        Some(symbol) => format!(
+            "{}symbol={},{}",
+            &uri[..uri.len() - symbol.len()],
+            symbol,
+            param
+        ),
+        None => format!("{}:{}", uri, param),
+    }
+}
+
+/// Canonical form of a "{scheme}:{endpoint}:{params}" URI, so that URIs
+/// naming the same request match: the bare form "binance:ticker:BTC/USDT"
+/// becomes `symbol=BTCUSDT`, symbols are normalized and params are sorted
+fn canonical_uri(uri: &str) -> String {
+    let mut parts = uri.splitn(3, ':');
+    let (Some(scheme), Some(endpoint), Some(params)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return uri.to_string();
+    };
+
+    let mut pairs: Vec<(&str, String)> = if params.contains('=') {
+        params
+            .split(',')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (key, value.to_string())
+            })
+            .collect()
+    } else {
+        vec![("symbol", params.to_string())]
+    };
+    for (key, value) in pairs.iter_mut() {
+        if *key == "symbol" {
+            if let Ok(symbol) = normalize_pair(value) {
+                *value = symbol;
+            }
+        }
+    }
+    pairs.sort();
+
+    let params: Vec<String> = pairs.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+    format!("{}:{}:{}", scheme, endpoint, params.join(","))
+}
+
+/// Per-cycle state of the strategy resolver
+#[derive(Default)]
+struct CycleState {
+    cycle: u32,
+    seq: u16,
+    market_data: HashMap<String, Tensor>,
+    report: CycleReport,
+}
+
+/// Resolver the strategy graph runs against
+///
+/// Serves the cycle's market data to the graph's market data nodes, routes
+/// `http:` URIs to the HTTP resolver and the connector's own URIs to the
+/// connector, and gates order endpoints: orders whose inputs carry less than
+/// `min_confidence` are skipped, and the rest are tagged with client order
+/// IDs and sent to the connector. In paper mode they're sent as test orders
+/// (`test=true`), which run the connector's checks without submitting, and
+/// the balance is a flat paper balance. A rejected order is logged and
+/// reported as `ORDER_STATUS_REJECTED`, so the rest of the graph still runs.
+struct StrategyResolver {
+    venue: String,
+    connector: Arc<dyn ExternalResolver>,
+    http: HttpResolver,
+    paper_mode: bool,
+    min_confidence: f32,
+    client_ids: ClientOrderIdGenerator,
+    state: Mutex<CycleState>,
+}
+
+impl StrategyResolver {
+    fn state(&self) -> Result<MutexGuard<'_, CycleState>, String> {
+        self.state
+            .lock()
+            .map_err(|_| "Cycle state poisoned".to_string())
+    }
+
+    /// Start a cycle with the market data fetched for it
+    fn begin_cycle(&self, cycle: u32, market_data: HashMap<String, Tensor>) -> Result<(), String> {
+        *self.state()? = CycleState {
+            cycle,
+            market_data,
+            ..Default::default()
+        };
+        Ok(())
+    }
+
+    /// Finish a cycle and return what happened to its orders
+    fn end_cycle(&self) -> Result<CycleReport, String> {
+        let mut state = self.state()?;
+        state.market_data.clear();
+        Ok(std::mem::take(&mut state.report))
+    }
+
+    /// Reserve the next `count` client order IDs of the cycle
+    fn next_client_ids(&self, count: usize) -> Result<Vec<String>, String> {
+        let mut state = self.state()?;
+        let ids = (0..count)
+            .map(|i| self.client_ids.generate(state.cycle, state.seq.wrapping_add(i as u16)))
+            .collect();
+        state.seq = state.seq.wrapping_add(count as u16);
+        Ok(ids)
+    }
+
+    /// Gate, then place or test an `order` or `batch_order` request
+    fn place(&self, uri: &str, endpoint: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+        let batch = endpoint == "batch_order";
+        let rows = if batch {
+            inputs.first().map_or(0, |prices| prices.data.len())
+        } else {
+            1
+        };
+        let confidence = inputs.iter().map(|t| t.confidence).fold(1.0, f32::min);
+        let status = |status: f32| {
+            let shape = if batch { vec![rows as u32, 4] } else { vec![4] };
+            Tensor::new(shape, [0.0, status, 0.0, 0.0].repeat(rows), confidence)
+        };
+
+        if confidence < self.min_confidence {
+            tracing::info!(
+                "Skipping {}: input confidence {:.2} is below {:.2}",
+                uri,
+                confidence,
+                self.min_confidence
+            );
+            self.state()?.report.skipped += rows;
+            return Ok(status(ORDER_STATUS_SKIPPED));
+        }
+
+        let ids = self.next_client_ids(rows)?;
+        let tagged = if batch {
+            with_param(uri, &format!("client_order_ids={}", ids.join(".")))
+        } else {
+            with_param(uri, &format!("client_order_id={}", ids[0]))
+        };
+        let (tagged, kind) = if self.paper_mode {
+            (with_param(&tagged, "test=true"), "Paper order")
+        } else {
+            (tagged, "Order")
+        };
+        match self.connector.resolve(&tagged, inputs) {
+            Ok(response) => {
+                let rejected = if batch {
+                    response
+                        .data
+                        .chunks(4)
+                        .filter(|row| row.get(1) == Some(&ORDER_STATUS_REJECTED))
+                        .count()
+                } else {
+                    0
+                };
+                let mut state = self.state()?;
+                state.report.submitted += rows - rejected;
+                state.report.rejected += rejected;
+                tracing::info!("{} {} placed", kind, ids.join(","));
+                Ok(response)
+            }
+            Err(e) => {
+                tracing::warn!("{} {} rejected: {}", kind, ids.join(","), e);
+                self.state()?.report.rejected += rows;
+                Ok(status(ORDER_STATUS_REJECTED))
+            }
+        }
+    }
+}
+
+impl ExternalResolver for StrategyResolver {
+    fn resolve(&self, uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+        let mut parts = uri.splitn(3, ':');
+        let scheme = parts.next().unwrap_or_default();
+        let endpoint = parts.next().unwrap_or_default();
+
+        if scheme == "http" {
+            return self.http.resolve(uri, inputs);
+        }
+        if scheme != self.venue {
+            return Err(format!(
+                "No connector for {} (running on {})",
+                scheme, self.venue
+            ));
+        }
+
+        let cached = self.state()?.market_data.get(&canonical_uri(uri)).cloned();
+        if let Some(market_data) = cached {
+            return Ok(market_data);
+        }
+
+        match endpoint {
+            "order" | "batch_order" => self.place(uri, endpoint, inputs),
+            "balance" if self.paper_mode => {
+                tracing::debug!("Paper balance for {}: flat", uri);
+                Ok(Tensor::new(vec![2], vec![0.0, 0.0], 1.0)) // [free, locked]
+            }
+            _ => self.connector.resolve(uri, inputs),
+        }
+    }
 }
 
 /// The trading runtime
 pub struct TradingRuntime {
     config: RuntimeConfig,
-    vm: VM,
-    http_resolver: Arc<HttpResolver>,
+    /// Connector the runtime fetches market data from
+    connector: Arc<dyn ExternalResolver>,
+    /// Resolver the strategy graph runs against
+    resolver: Arc<StrategyResolver>,
+    /// Records what `resolver` returns to the graph when `record_path` is set
+    recording: Option<Recording>,
+    symbol: String,
+    retry: RetryConfig,
 }
 
 impl TradingRuntime {
     /// Create a new trading runtime
-    pub fn new(config: RuntimeConfig) -> Self {
-        let http_resolver = Arc::new(HttpResolver::new());
-        let vm = VM::new().with_external_resolver(http_resolver.clone() as Arc<dyn ExternalResolver>);
+    pub fn new(config: RuntimeConfig) -> Result<Self, String> {
+        // Only Binance exposes ticker/orderbook endpoints to poll
+        if config.connector.venue != "binance" {
+            return Err(format!(
+                "Connector {} has no market data endpoints (supported: binance)",
+                config.connector.venue
+            ));
+        }
 
-        Self {
+        let connector = config.connector.build()?;
+        Self::with_connector(config, connector)
+    }
+
+    /// Create a runtime around an already-built connector resolver
+    pub fn with_connector(
+        config: RuntimeConfig,
+        connector: Arc<dyn ExternalResolver>,
+    ) -> Result<Self, String> {
+        let symbol = normalize_pair(&config.pair)?;
+        let retry = config.connector.retry()?;
+        // Fail before the loop starts if orders on the pair can't be validated
+        connector.resolve(&format!("{}:exchange_info:{}", config.connector.venue, symbol), vec![])?;
+
+        let graph_bytes = std::fs::read(&config.strategy_path)
+            .map_err(|e| format!("Failed to read {}: {}", config.strategy_path, e))?;
+        let tag = Path::new(&config.strategy_path)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("strategy");
+
+        let resolver = Arc::new(StrategyResolver {
+            venue: config.connector.venue.clone(),
+            connector: connector.clone(),
+            http: HttpResolver::new().with_environment(config.connector.environment),
+            paper_mode: config.paper_mode,
+            min_confidence: config.min_confidence,
+            client_ids: ClientOrderIdGenerator::new(tag, &graph_bytes),
+            state: Mutex::new(CycleState::default()),
+        });
+
+        let recording = match &config.record_path {
+            Some(path) => Some(Recording {
+                resolver: CachingResolver::recording(resolver.clone()),
+                file: File::create(path)
+                    .map_err(|e| format!("Failed to create recording {}: {}", path, e))?,
+                path: path.clone(),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
             config,
-            vm,
-            http_resolver,
-        }
+            connector,
+            resolver,
+            recording,
+            symbol,
+            retry,
+        })
     }
 
     /// Load a strategy graph from file
-    pub fn load_strategy(&self, _path: &Path) -> Result<RuntimeGraph, String> {
-        // TODO: Implement graph loading from .0 files
-        Err("Graph loading not yet implemented".to_string())
+    ///
+    /// The file must define exactly one graph using only operations the
+    /// interpreter supports, and every connector node that names a symbol
+    /// must name the runtime's pair. The configured parameters
+    /// replace the graph's `config_*` constants.
+    pub fn load_strategy(&self, path: &Path) -> Result<GraphSummary, String> {
+        let mut file = GraphFile::load(path)?;
+        if file.graphs.len() != 1 {
+            return Err(format!(
+                "{} defines {} graphs; a strategy must define exactly one",
+                path.display(),
+                file.graphs.len()
+            ));
+        }
+        let mut graph = file.graphs.remove(0);
+        interpreter::check_ops(&graph)?;
+
+        for (name, value) in &self.config.params {
+            graph
+                .set_constant(&format!("{}{}", PARAM_PREFIX, name), *value as f32)
+                .map_err(|e| format!("Parameter {}: {}", name, e))?;
+        }
+
+        let venue = self.config.connector.venue.as_str();
+        for node in graph.nodes.iter().filter(|n| n.node_type == NodeType::External) {
+            let Some(uri) = node.uri.as_deref() else {
+                continue;
+            };
+            let mut parts = uri.splitn(3, ':');
+            let (Some(scheme), Some(endpoint), Some(params)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            // Account endpoints take an asset, not a symbol
+            if scheme != venue || endpoint == "balance" || endpoint.starts_with("margin_") {
+                continue;
+            }
+
+            let symbol = if params.contains('=') {
+                uri_param(uri, "symbol")
+            } else {
+                Some(params)
+            };
+            if let Some(symbol) = symbol {
+                if normalize_pair(symbol)? != self.symbol {
+                    return Err(format!(
+                        "Node '{}' trades {} but the runtime pair is {}",
+                        node.id, symbol, self.symbol
+                    ));
+                }
+            }
+        }
+
+        Ok(graph)
     }
 
     /// Execute a single iteration of the strategy
-    pub fn execute_once(&mut self, graph: &RuntimeGraph) -> Result<Vec<Tensor>, String> {
-        self.vm
-            .execute(graph)
+    pub fn execute_once(&self, graph: &GraphSummary) -> Result<Vec<Tensor>, String> {
+        let resolver: &dyn ExternalResolver = match &self.recording {
+            Some(recording) => &recording.resolver,
+            None => self.resolver.as_ref(),
+        };
+        interpreter::evaluate(graph, resolver)
             .map_err(|e| format!("Execution error: {}", e))
     }
 
-    /// Run the strategy continuously
+    /// Canonical URIs of the market data fetched each cycle: the pair's
+    /// ticker and orderbook (for the cycle snapshot), then every other
+    /// ticker or orderbook the graph reads. The snapshot orderbook is the
+    /// graph's first, at the depth the graph asks for.
+    fn market_data_uris(&self, graph: &GraphSummary) -> Vec<String> {
+        let venue = &self.config.connector.venue;
+        let mut books = Vec::new();
+        let mut others = Vec::new();
+        for node in graph.nodes.iter().filter(|n| n.node_type == NodeType::External) {
+            let Some(uri) = node.uri.as_deref() else {
+                continue;
+            };
+            let mut parts = uri.splitn(3, ':');
+            match (parts.next(), parts.next()) {
+                (Some(scheme), Some("orderbook" | "depth")) if scheme == venue => {
+                    books.push(canonical_uri(uri))
+                }
+                (Some(scheme), Some("ticker")) if scheme == venue => others.push(canonical_uri(uri)),
+                _ => {}
+            }
+        }
+
+        let mut uris = vec![format!("{}:ticker:symbol={}", venue, self.symbol)];
+        if books.is_empty() {
+            uris.push(format!("{}:orderbook:limit=1,symbol={}", venue, self.symbol));
+        }
+        for uri in books.into_iter().chain(others) {
+            if !uris.contains(&uri) {
+                uris.push(uri);
+            }
+        }
+        uris
+    }
+
+    /// Fetch the cycle's market data, keyed by canonical URI
+    fn fetch_market_tensors(&self, uris: &[String]) -> Result<HashMap<String, Tensor>, String> {
+        uris.iter()
+            .map(|uri| Ok((uri.clone(), self.connector.resolve(uri, vec![])?)))
+            .collect()
+    }
+
+    /// Run one cycle: fetch market data, then evaluate the graph against it.
+    /// Orders are placed by the graph's order nodes as it runs. Fails only
+    /// if market data can't be fetched; a graph that fails partway still
+    /// reports the orders it placed, with the error in `CycleReport::error`.
+    pub async fn run_cycle(&self, graph: &GraphSummary, cycle: u32) -> Result<CycleReport, String> {
+        let uris = self.market_data_uris(graph);
+        let market_data = retry_with_backoff(
+            &self.retry,
+            || async { self.fetch_market_tensors(&uris) },
+            |e: &String| is_transient(e),
+        )
+        .await?;
+
+        let snapshot = MarketSnapshot::from_tensors(&market_data[&uris[0]], &market_data[&uris[1]])?;
+        tracing::info!(
+            "Cycle {}: {} last {} bid {} ask {} (confidence {})",
+            cycle,
+            self.symbol,
+            snapshot.last_price,
+            snapshot.best_bid,
+            snapshot.best_ask,
+            snapshot.confidence
+        );
+
+        self.resolver.begin_cycle(cycle, market_data)?;
+        let outputs = self.execute_once(graph);
+        let mut report = self.resolver.end_cycle()?;
+
+        match outputs {
+            Ok(outputs) => tracing::debug!("Cycle {} outputs: {:?}", cycle, outputs),
+            Err(e) => report.error = Some(e),
+        }
+        Ok(report)
+    }
+
+    /// Run the strategy continuously until Ctrl-C
     pub async fn run(&mut self) -> Result<(), String> {
         println!("┌─────────────────────────────────────────────────────────────┐");
         println!("│  TRADING RUNTIME                                            │");
         println!("├─────────────────────────────────────────────────────────────┤");
         println!("│  Strategy: {}", self.config.strategy_path);
-        println!("│  Connector: {}", self.config.connector);
+        println!("│  Connector: {}", self.config.connector.venue);
+        println!("│  Environment: {:?}", self.config.connector.environment);
         println!("│  Pair: {}", self.config.pair);
         println!("│  Interval: {}ms", self.config.interval_ms);
         println!("│  Mode: {}", if self.config.paper_mode { "Paper" } else { "Live" });
-        println!("├─────────────────────────────────────────────────────────────┤");
-        println!("│  Status: Runtime loop not yet implemented                   │");
+        println!("│  Min confidence: {}", self.config.min_confidence);
+        if let Some(path) = &self.config.record_path {
+            println!("│  Recording to: {}", path);
+        }
         println!("└─────────────────────────────────────────────────────────────┘");
 
-        // TODO: Implement the execution loop
-        // 1. Load strategy graph
-        // 2. Fetch market data
-        // 3. Execute graph
-        // 4. Process decision tensor
-        // 5. Place orders if confidence > threshold
-        // 6. Sleep for interval
-        // 7. Repeat
+        self.run_until(tokio::signal::ctrl_c()).await.map(|_| ())
+    }
+
+    /// Append the External results recorded since the last flush to the
+    /// recording, if recording
+    fn flush_recording(&self) -> Result<(), String> {
+        let Some(recording) = &self.recording else {
+            return Ok(());
+        };
 
+        let entries = recording
+            .resolver
+            .cache()
+            .lock()
+            .map_err(|_| "Resolver cache poisoned".to_string())?
+            .take_recorded();
+        ResolverCache::write_jsonl(&entries, &recording.file)
+            .map_err(|e| format!("{}: {}", recording.path, e))?;
+        tracing::debug!("Recorded {} External results to {}", entries.len(), recording.path);
         Ok(())
     }
-}
-
-/// Order decision from strategy execution
-#[derive(Debug)]
-pub struct OrderDecision {
-    /// Whether to place an order
-    pub should_order: bool,
-    /// Order side (buy/sell)
-    pub side: OrderSide,
-    /// Order quantity
-    pub quantity: f32,
-    /// Order price (for limit orders)
-    pub price: Option<f32>,
-    /// Confidence level
-    pub confidence: f32,
-}
 
-/// Order side
-#[derive(Debug, Clone, Copy)]
-pub enum OrderSide {
-    Buy,
-    Sell,
-}
+    /// Replay a recording made with `record_path`: evaluate the graph once
+    /// per recorded cycle against the recorded External results, until the
+    /// recording is used up. Returns the number of cycles replayed.
+    pub fn replay(&self, path: &Path) -> Result<u32, String> {
+        let graph = self.load_strategy(Path::new(&self.config.strategy_path))?;
+        let file = File::open(path)
+            .map_err(|e| format!("Failed to open recording {}: {}", path.display(), e))?;
+        let replayer = CachingResolver::replaying(ResolverCache::read_jsonl(BufReader::new(file))?);
+        let used_up = || {
+            replayer
+                .cache()
+                .lock()
+                .map(|cache| cache.is_empty())
+                .map_err(|_| "Resolver cache poisoned".to_string())
+        };
 
-impl OrderDecision {
-    /// Create from strategy output tensor
-    pub fn from_tensor(tensor: &Tensor) -> Option<Self> {
-        // Expected tensor format:
-        // [should_order, side, quantity, price] with confidence
-        
-        if tensor.data.len() < 4 {
-            return None;
+        let mut cycle: u32 = 0;
+        while !used_up()? {
+            // A recorded cycle whose graph failed replays the same failure
+            match interpreter::evaluate(&graph, &replayer) {
+                Ok(outputs) => tracing::info!("Replayed cycle {}: {:?}", cycle, outputs),
+                Err(e) if e.contains("No recorded result") => {
+                    return Err(format!("Replay of cycle {} diverged from the recording: {}", cycle, e));
+                }
+                Err(e) => tracing::warn!("Replayed cycle {} failed: {}", cycle, e),
+            }
+            cycle += 1;
         }
 
-        let should_order = tensor.data[0] > 0.5;
-        let side = if tensor.data[1] > 0.5 {
-            OrderSide::Buy
-        } else {
-            OrderSide::Sell
-        };
-        let quantity = tensor.data[2];
-        let price = if tensor.data[3] > 0.0 {
-            Some(tensor.data[3])
-        } else {
-            None
-        };
+        Ok(cycle)
+    }
 
-        Some(Self {
-            should_order,
-            side,
-            quantity,
-            price,
-            confidence: tensor.confidence,
-        })
+    /// Run cycles on the configured interval until `shutdown` completes.
+    /// Returns the number of cycles run.
+    pub async fn run_until<F: Future>(&mut self, shutdown: F) -> Result<u32, String> {
+        let graph = self.load_strategy(Path::new(&self.config.strategy_path))?;
+
+        let mut ticker = tokio::time::interval(Duration::from_millis(self.config.interval_ms.max(1)));
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        tokio::pin!(shutdown);
+
+        let mut cycle: u32 = 0;
+        loop {
+            tokio::select! {
+                _ = &mut shutdown => {
+                    tracing::info!("Shutting down after {} cycles", cycle);
+                    self.flush_recording()?;
+                    return Ok(cycle);
+                }
+                _ = ticker.tick() => {
+                    // A failed cycle is logged and retried on the next tick
+                    match self.run_cycle(&graph, cycle).await {
+                        Ok(report) => {
+                            tracing::info!(
+                                "Cycle {}: {} orders submitted, {} skipped, {} rejected",
+                                cycle, report.submitted, report.skipped, report.rejected
+                            );
+                            if let Some(e) = &report.error {
+                                tracing::error!("Cycle {} stopped early: {}", cycle, e);
+                            }
+                        }
+                        Err(e) => tracing::error!("Cycle {} failed: {}", cycle, e),
+                    }
+                    self.flush_recording()?;
+                    cycle = cycle.wrapping_add(1);
+                }
+            }
+        }
     }
 }
 
+/// Recording of what the graph sees, appended to after every cycle
+struct Recording {
+    resolver: CachingResolver,
+    file: File,
+    path: String,
+}
+
+/// Errors worth retrying within a cycle (rate limits, timeouts, dropped connections)
+fn is_transient(error: &str) -> bool {
+    let error = error.to_lowercase();
+    ["429", "418", "rate limit", "timeout", "timed out", "connection"]
+        .iter()
+        .any(|pattern| error.contains(pattern))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::resolvers::config::RateLimitConfig;
+    use crate::resolvers::BinanceResolver;
+    use crate::strategies::STRATEGY_DIR;
+
+    fn config(strategy: &str, venue: &str, paper_mode: bool) -> RuntimeConfig {
+        let strategy_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join(STRATEGY_DIR)
+            .join(format!("{}.0", strategy));
+
+        RuntimeConfig {
+            strategy_path: strategy_path.to_string_lossy().into_owned(),
+            connector: ConnectorConfig {
+                // Retry rate-limited requests without slowing the tests down
+                rate_limit: RateLimitConfig {
+                    base_delay_ms: Some(1),
+                    ..Default::default()
+                },
+                ..ConnectorConfig::new(venue)
+            },
+            pair: "BTC/USDT".to_string(),
+            interval_ms: 1000,
+            paper_mode,
+            // Accept the placeholder market data's 0.5 confidence
+            min_confidence: 0.5,
+            params: HashMap::new(),
+            record_path: None,
+        }
+    }
+
+    /// Binance connector that records URIs and rate-limits its first calls
+    struct Recorder {
+        inner: BinanceResolver,
+        rate_limited: Mutex<u32>,
+        uris: Mutex<Vec<String>>,
+    }
+
+    impl Recorder {
+        fn new(inner: BinanceResolver, rate_limited: u32) -> Arc<Self> {
+            Arc::new(Self {
+                inner,
+                rate_limited: Mutex::new(rate_limited),
+                uris: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn calls(&self, pattern: &str) -> Vec<String> {
+            let uris = self.uris.lock().unwrap();
+            uris.iter().filter(|u| u.contains(pattern)).cloned().collect()
+        }
+    }
+
+    impl ExternalResolver for Recorder {
+        fn resolve(&self, uri: &str, inputs: Vec<&Tensor>) -> Result<Tensor, String> {
+            self.uris.lock().unwrap().push(uri.to_string());
+            let mut rate_limited = self.rate_limited.lock().unwrap();
+            if *rate_limited > 0 && uri.contains(":ticker:") {
+                *rate_limited -= 1;
+                return Err("HTTP 429 Too Many Requests".to_string());
+            }
+            self.inner.resolve(uri, inputs)
+        }
+    }
+
+    fn recorded(config: RuntimeConfig, connector: Arc<Recorder>) -> TradingRuntime {
+        TradingRuntime::with_connector(config, connector).unwrap()
+    }
+
+    async fn one_cycle(runtime: &TradingRuntime) -> Result<CycleReport, String> {
+        let graph = runtime.load_strategy(Path::new(&runtime.config.strategy_path))?;
+        runtime.run_cycle(&graph, 0).await
+    }
 
     #[test]
-    fn test_order_decision_from_tensor() {
-        let tensor = Tensor {
-            shape: vec![4],
-            data: vec![1.0, 1.0, 0.5, 100.0], // should_order=true, buy, 0.5 qty, $100
-            confidence: 0.9,
-        };
+    fn test_market_data_follows_the_graph() {
+        let runtime = TradingRuntime::new(config("market_making", "binance", true)).unwrap();
+        let graph = runtime.load_strategy(Path::new(&runtime.config.strategy_path)).unwrap();
+
+        // The orderbook is fetched at the depth the graph's node asks for
+        let uris = runtime.market_data_uris(&graph);
+        assert_eq!(
+            uris,
+            vec!["binance:ticker:symbol=BTCUSDT", "binance:orderbook:limit=5,symbol=BTCUSDT"]
+        );
+        let market_data = runtime.fetch_market_tensors(&uris).unwrap();
+        assert_eq!(market_data[&uris[1]].shape, vec![10, 2]);
+        let snapshot = MarketSnapshot::from_tensors(&market_data[&uris[0]], &market_data[&uris[1]]).unwrap();
+        assert_eq!(snapshot.last_price, 50000.0);
+        assert_eq!(snapshot.best_bid, 49990.0);
+        assert_eq!(snapshot.best_ask, 50010.0);
+
+        // A graph without an orderbook node still gets top of book
+        let runtime = TradingRuntime::new(config("grid_trading", "binance", true)).unwrap();
+        let graph = runtime.load_strategy(Path::new(&runtime.config.strategy_path)).unwrap();
+        assert_eq!(
+            runtime.market_data_uris(&graph),
+            vec!["binance:ticker:symbol=BTCUSDT", "binance:orderbook:limit=1,symbol=BTCUSDT"]
+        );
+    }
+
+    #[test]
+    fn test_canonical_uri() {
+        let canonical = "binance:orderbook:limit=5,symbol=BTCUSDT";
+        assert_eq!(canonical_uri("binance:orderbook:symbol=BTCUSDT,limit=5"), canonical);
+        assert_eq!(canonical_uri("binance:orderbook:limit=5,symbol=btc/usdt"), canonical);
+        assert_eq!(canonical_uri("binance:ticker:BTC-USDT"), "binance:ticker:symbol=BTCUSDT");
+        assert_eq!(canonical_uri("binance:exchange_info"), "binance:exchange_info");
+    }
+
+    #[test]
+    fn test_new_rejects_unsupported_connector() {
+        assert!(TradingRuntime::new(config("market_making", "okx", true)).is_err());
+
+        let mut bad_pair = config("market_making", "binance", true);
+        bad_pair.pair = "FOO/USDT".to_string();
+        assert!(TradingRuntime::new(bad_pair).is_err());
+    }
+
+    #[test]
+    fn test_load_strategy_checks_graph_and_pair() {
+        let runtime = TradingRuntime::new(config("market_making", "binance", true)).unwrap();
+        let path = Path::new(&runtime.config.strategy_path);
+        assert_eq!(runtime.load_strategy(path).unwrap().outputs, vec!["output"]);
+
+        // The connector graphs file defines three graphs
+        let connectors = Path::new(env!("CARGO_MANIFEST_DIR")).join("graphs/connectors/binance.0");
+        assert!(runtime.load_strategy(&connectors).is_err());
+
+        let mut eth = config("market_making", "binance", true);
+        eth.pair = "ETH/USDT".to_string();
+        let runtime = TradingRuntime::new(eth).unwrap();
+        let err = runtime.load_strategy(path).unwrap_err();
+        assert!(err.contains("BTCUSDT"), "{}", err);
+    }
 
-        let decision = OrderDecision::from_tensor(&tensor).unwrap();
-        
-        assert!(decision.should_order);
-        assert!(matches!(decision.side, OrderSide::Buy));
-        assert_eq!(decision.quantity, 0.5);
-        assert_eq!(decision.price, Some(100.0));
-        assert_eq!(decision.confidence, 0.9);
+    #[tokio::test]
+    async fn test_paper_cycle_simulates_graph_orders() {
+        for (strategy, orders) in [("market_making", 2), ("grid_trading", 10)] {
+            let connector = Recorder::new(BinanceResolver::new(), 0);
+            let runtime = recorded(config(strategy, "binance", true), connector.clone());
+
+            let report = one_cycle(&runtime).await.unwrap();
+            assert_eq!(report.submitted, orders, "{}", strategy);
+            assert_eq!(report.rejected, 0, "{}", strategy);
+
+            // Market data is fetched once and served to the graph, orders
+            // reach the connector only as test orders, and the paper balance
+            // never does
+            assert_eq!(connector.calls(":ticker:").len(), 1);
+            let orders = [connector.calls(":order:"), connector.calls(":batch_order:")].concat();
+            assert!(!orders.is_empty());
+            assert!(orders.iter().all(|uri| uri.ends_with(",test=true")), "{:?}", orders);
+            assert!(connector.calls(":balance:").is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_params_override_graph_constants() {
+        let mut sized = config("grid_trading", "binance", true);
+        sized.params = HashMap::from([("order_size".to_string(), 0.25)]);
+        let runtime = recorded(sized, Recorder::new(BinanceResolver::new(), 0));
+
+        let graph = runtime.load_strategy(Path::new(&runtime.config.strategy_path)).unwrap();
+        let size = graph.node("config_order_size").unwrap().value.as_ref().unwrap();
+        assert_eq!(size.data, vec![0.25]);
+
+        let mut unknown = config("grid_trading", "binance", true);
+        unknown.params = HashMap::from([("spread".to_string(), 0.1)]);
+        let runtime = recorded(unknown, Recorder::new(BinanceResolver::new(), 0));
+        assert!(runtime.load_strategy(Path::new(&runtime.config.strategy_path)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_default_gate_skips_placeholder_data() {
+        let mut defaults = config("market_making", "binance", true);
+        defaults.min_confidence = DEFAULT_MIN_CONFIDENCE;
+        let connector = Recorder::new(BinanceResolver::new(), 0);
+        let runtime = recorded(defaults, connector.clone());
+
+        let report = one_cycle(&runtime).await.unwrap();
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.submitted, 0);
+        assert!(connector.calls(":order:").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_graph_still_reports_orders() {
+        // The arbitrage graph also reads OKX, which a Binance runtime can't serve
+        let runtime = recorded(config("arbitrage", "binance", true), Recorder::new(BinanceResolver::new(), 0));
+
+        let report = one_cycle(&runtime).await.unwrap();
+        let error = report.error.unwrap();
+        assert!(error.contains("No connector for okx"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn test_low_confidence_orders_are_skipped() {
+        let mut strict = config("market_making", "binance", true);
+        strict.min_confidence = 0.9;
+        let runtime = recorded(strict, Recorder::new(BinanceResolver::new(), 0));
+
+        let report = one_cycle(&runtime).await.unwrap();
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.submitted, 0);
+    }
+
+    #[tokio::test]
+    async fn test_live_cycle_tags_orders_and_survives_rejections() {
+        let connector = Recorder::new(BinanceResolver::with_credentials("key", "secret"), 0);
+        let runtime = recorded(config("market_making", "binance", false), connector.clone());
+
+        let report = one_cycle(&runtime).await.unwrap();
+        assert_eq!(report.submitted, 2);
+        let orders = connector.calls(":order:");
+        assert_eq!(orders.len(), 2);
+        assert!(orders.iter().all(|uri| uri.contains(",client_order_id=market")));
+        assert_ne!(orders[0], orders[1]);
+
+        // Without credentials both orders are rejected, but the cycle completes
+        let connector = Recorder::new(BinanceResolver::new(), 0);
+        let runtime = recorded(config("market_making", "binance", false), connector.clone());
+        let report = one_cycle(&runtime).await.unwrap();
+        assert_eq!(report.rejected, 2);
+        assert_eq!(connector.calls(":order:").len(), 2);
+    }
+
+    #[test]
+    fn test_paper_orders_enforce_max_slippage() {
+        let mut bounded = config("market_making", "binance", true);
+        bounded.connector.max_slippage_bps = Some(1.0);
+        let runtime = TradingRuntime::new(bounded).unwrap();
+        runtime.resolver.begin_cycle(0, HashMap::new()).unwrap();
+
+        // A 0.2 BTC market buy fills ~3 bps from mid
+        let market = Tensor::scalar(0.0, 1.0);
+        let quantity = Tensor::scalar(0.2, 1.0);
+        let uri = "binance:order:symbol=BTCUSDT,side=BUY";
+        let response = runtime.resolver.resolve(uri, vec![&market, &quantity]).unwrap();
+        assert_eq!(response.data[1], ORDER_STATUS_REJECTED);
+
+        // Limit orders aren't subject to the slippage limit
+        let limit = Tensor::scalar(49990.0, 1.0);
+        let response = runtime.resolver.resolve(uri, vec![&limit, &quantity]).unwrap();
+        assert_eq!(response.data[1], 1.0);
+
+        let report = runtime.resolver.end_cycle().unwrap();
+        assert_eq!((report.submitted, report.rejected), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_cycle_retries_rate_limited_market_data() {
+        let connector = Recorder::new(BinanceResolver::new(), 2);
+        let runtime = recorded(config("market_making", "binance", true), connector.clone());
+
+        assert!(one_cycle(&runtime).await.is_ok());
+        assert_eq!(connector.calls(":ticker:").len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_until_shutdown() {
+        let mut fast = config("market_making", "binance", true);
+        fast.interval_ms = 1;
+        let mut runtime = recorded(fast, Recorder::new(BinanceResolver::new(), 0));
+
+        let cycles = runtime
+            .run_until(tokio::time::sleep(Duration::from_millis(50)))
+            .await
+            .unwrap();
+        assert!(cycles >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_recording_is_flushed_every_cycle() {
+        let path = std::env::temp_dir().join(format!("zero-hummingbot-flush-{}.jsonl", std::process::id()));
+        std::fs::write(&path, "stale\n").unwrap();
+        let mut recording = config("market_making", "binance", true);
+        recording.record_path = Some(path.to_string_lossy().into_owned());
+        let runtime = recorded(recording, Recorder::new(BinanceResolver::new(), 0));
+        let lines = || std::fs::read_to_string(&path).unwrap().lines().count();
+
+        // Truncated at start, then appended to cycle by cycle
+        assert_eq!(lines(), 0);
+        let graph = runtime.load_strategy(Path::new(&runtime.config.strategy_path)).unwrap();
+        runtime.run_cycle(&graph, 0).await.unwrap();
+        runtime.flush_recording().unwrap();
+        let first = lines();
+        assert!(first > 0);
+        runtime.run_cycle(&graph, 1).await.unwrap();
+        runtime.flush_recording().unwrap();
+        assert_eq!(lines(), first * 2);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_replay_recorded_run() {
+        let path = std::env::temp_dir().join(format!("zero-hummingbot-replay-{}.jsonl", std::process::id()));
+        let mut recording = config("market_making", "binance", true);
+        recording.interval_ms = 1;
+        recording.record_path = Some(path.to_string_lossy().into_owned());
+        let mut runtime = recorded(recording, Recorder::new(BinanceResolver::new(), 0));
+        let cycles = runtime
+            .run_until(tokio::time::sleep(Duration::from_millis(30)))
+            .await
+            .unwrap();
+
+        // Replaying needs no connector calls beyond the startup check
+        let connector = Recorder::new(BinanceResolver::new(), 0);
+        let replaying = recorded(config("market_making", "binance", true), connector.clone());
+        assert_eq!(replaying.replay(&path).unwrap(), cycles);
+        assert!(connector.calls(":ticker:").is_empty());
+        assert!(connector.calls(":order:").is_empty());
+
+        // A graph that asks for something else diverges from the recording
+        let mut resized = config("market_making", "binance", true);
+        resized.params = HashMap::from([("order_size".to_string(), 0.5)]);
+        let replaying = recorded(resized, Recorder::new(BinanceResolver::new(), 0));
+        let err = replaying.replay(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(err.contains("diverged"), "{}", err);
+    }
+
+    #[test]
+    fn test_strategy_resolver_routing() {
+        let runtime = TradingRuntime::new(config("market_making", "binance", true)).unwrap();
+        let resolver = runtime.resolver.as_ref();
+
+        assert!(resolver.resolve("http:get:binance:/api/v3/time", vec![]).is_ok());
+        assert!(resolver.resolve("okx:ticker:BTCUSDT", vec![]).is_err());
+        assert!(resolver.resolve("binance:ticker:BTCUSDT", vec![]).is_ok());
+    }
+
+    #[test]
+    fn test_uri_params() {
+        let uri = "binance:order:symbol=BTCUSDT,side=BUY";
+        assert_eq!(uri_param(uri, "side"), Some("BUY"));
+        assert_eq!(uri_param(uri, "symbol"), Some("BTCUSDT"));
+        assert_eq!(uri_param(uri, "price"), None);
+
+        assert_eq!(with_param(uri, "a=1"), "binance:order:symbol=BTCUSDT,side=BUY,a=1");
+        assert_eq!(with_param("binance:order:BTCUSDT", "a=1"), "binance:order:symbol=BTCUSDT,a=1");
+        assert_eq!(with_param("binance:order", "a=1"), "binance:order:a=1");
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient("HTTP 429 Too Many Requests"));
+        assert!(is_transient("request timed out"));
+        assert!(!is_transient("API credentials required for placing orders"));
     }
 }
@@ -0,0 +1,554 @@
+//! Strategy registry
+//!
+//! Finds strategy graphs on disk, reads their declared metadata, and
+//! resolves strategies by name along with their parameter schemas.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::graph::{self, NodeType};
+
+/// Directory holding the bundled strategy graphs, relative to the install root
+pub const STRATEGY_DIR: &str = "graphs/strategies";
+
+/// Environment variable overriding the install root the bundled graphs live under
+pub const ROOT_ENV: &str = "ZERO_HUMMINGBOT_ROOT";
+
+/// Locate the bundled strategy directory
+///
+/// `$ZERO_HUMMINGBOT_ROOT` wins when set. Otherwise the directories above the
+/// executable are searched (an installed tree or `target/{profile}` in a
+/// checkout), then the current directory.
+pub fn builtin_dir() -> Result<PathBuf, String> {
+    let root = std::env::var_os(ROOT_ENV).map(PathBuf::from);
+    let exe = std::env::current_exe().ok();
+    let cwd = std::env::current_dir().ok();
+    find_builtin_dir(root.as_deref(), exe.as_deref(), cwd.as_deref())
+}
+
+/// `builtin_dir` given the root override, executable path and current directory
+fn find_builtin_dir(root: Option<&Path>, exe: Option<&Path>, cwd: Option<&Path>) -> Result<PathBuf, String> {
+    if let Some(root) = root {
+        let dir = root.join(STRATEGY_DIR);
+        return if dir.is_dir() {
+            Ok(dir)
+        } else {
+            Err(format!("{} is set but {} does not exist", ROOT_ENV, dir.display()))
+        };
+    }
+
+    let exe_roots = exe.into_iter().flat_map(|exe| exe.ancestors().skip(1));
+
+    exe_roots
+        .chain(cwd)
+        .map(|root| root.join(STRATEGY_DIR))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| {
+            format!(
+                "Bundled strategies not found; set {} to the directory containing {}",
+                ROOT_ENV, STRATEGY_DIR
+            )
+        })
+}
+
+/// Resolve a strategy name or a path to a .0 file
+///
+/// Paths are checked first, so running a graph file by path doesn't depend
+/// on the bundled strategies being found.
+pub fn resolve(name_or_path: &str) -> Result<StrategyInfo, String> {
+    let path = Path::new(name_or_path);
+    if path.is_file() {
+        return StrategyInfo::from_file(path);
+    }
+
+    StrategyRegistry::builtin()?.resolve(name_or_path)
+}
+
+/// Value type of a strategy parameter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamKind {
+    Number,
+    Integer,
+}
+
+/// Prefix of the graph constants exposed as strategy parameters
+pub const PARAM_PREFIX: &str = "config_";
+
+/// Schema of one tunable strategy parameter (a `config_*` constant in the graph)
+#[derive(Debug, Clone, Serialize)]
+pub struct ParamSpec {
+    /// Parameter name (the graph constant without its `config_` prefix)
+    pub name: String,
+    /// Value type
+    pub kind: ParamKind,
+    /// Default value, matching the graph constant
+    pub default: f64,
+    /// Inclusive lower bound
+    pub min: f64,
+    /// Inclusive upper bound, if any
+    pub max: Option<f64>,
+    /// Human-readable description
+    pub description: String,
+}
+
+/// Metadata of a bundled strategy parameter; its default is the graph constant
+struct ParamMeta {
+    name: &'static str,
+    kind: ParamKind,
+    min: f64,
+    max: Option<f64>,
+    description: &'static str,
+}
+
+impl ParamMeta {
+    fn new(
+        name: &'static str,
+        kind: ParamKind,
+        min: f64,
+        max: Option<f64>,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            min,
+            max,
+            description,
+        }
+    }
+}
+
+impl ParamSpec {
+    /// Schema for a described graph constant
+    fn described(meta: &ParamMeta, default: f64) -> Self {
+        Self {
+            name: meta.name.to_string(),
+            kind: meta.kind,
+            default,
+            min: meta.min,
+            max: meta.max,
+            description: meta.description.to_string(),
+        }
+    }
+
+    /// Schema for a graph constant the strategy doesn't describe: any finite number
+    fn generic(name: &str, default: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            kind: ParamKind::Number,
+            default,
+            min: f64::NEG_INFINITY,
+            max: None,
+            description: format!("Graph constant {}{}", PARAM_PREFIX, name),
+        }
+    }
+
+    /// Parse and range-check a value for this parameter
+    pub fn parse(&self, value: &str) -> Result<f64, String> {
+        let parsed: f64 = value
+            .parse()
+            .map_err(|_| format!("Parameter {} expects a number, got: {}", self.name, value))?;
+
+        if self.kind == ParamKind::Integer && parsed.fract() != 0.0 {
+            return Err(format!("Parameter {} expects an integer, got: {}", self.name, value));
+        }
+
+        let in_range = parsed >= self.min && self.max.is_none_or(|max| parsed <= max);
+        if !parsed.is_finite() || !in_range {
+            let max = self.max.map_or("∞".to_string(), |max| max.to_string());
+            return Err(format!(
+                "Parameter {} out of range: {} (allowed: {}..={})",
+                self.name, value, self.min, max
+            ));
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// Parameter metadata of the bundled strategies (ranges and descriptions;
+/// the parameters themselves and their defaults come from the graph)
+fn builtin_params(strategy: &str) -> Vec<ParamMeta> {
+    match strategy {
+        "market_making" => vec![
+            ParamMeta::new(
+                "spread",
+                ParamKind::Number,
+                0.0,
+                Some(1.0),
+                "Spread per side as a fraction of mid price",
+            ),
+            ParamMeta::new(
+                "order_size",
+                ParamKind::Number,
+                0.0,
+                None,
+                "Order size in base asset",
+            ),
+            ParamMeta::new(
+                "confidence_threshold",
+                ParamKind::Number,
+                0.0,
+                Some(1.0),
+                "Minimum data confidence to quote",
+            ),
+            ParamMeta::new(
+                "max_position",
+                ParamKind::Number,
+                0.0,
+                None,
+                "Maximum inventory in base asset",
+            ),
+        ],
+        "grid_trading" => vec![
+            ParamMeta::new(
+                "num_levels",
+                ParamKind::Integer,
+                1.0,
+                Some(100.0),
+                "Grid levels on each side of mid price",
+            ),
+            ParamMeta::new(
+                "grid_spacing",
+                ParamKind::Number,
+                0.0,
+                Some(1.0),
+                "Spacing between levels as a fraction of price",
+            ),
+            ParamMeta::new(
+                "order_size",
+                ParamKind::Number,
+                0.0,
+                None,
+                "Order size per level in base asset",
+            ),
+        ],
+        "arbitrage" => vec![
+            ParamMeta::new(
+                "min_spread",
+                ParamKind::Number,
+                0.0,
+                Some(1.0),
+                "Minimum cross-venue spread to trade",
+            ),
+            ParamMeta::new(
+                "order_size",
+                ParamKind::Number,
+                0.0,
+                None,
+                "Order size in base asset",
+            ),
+            ParamMeta::new(
+                "fee_rate",
+                ParamKind::Number,
+                0.0,
+                Some(1.0),
+                "Fee rate per trade",
+            ),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Metadata for a strategy graph file
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyInfo {
+    /// Strategy name (the file stem)
+    pub name: String,
+    /// Graph name declared in the file
+    pub graph_name: Option<String>,
+    /// Description declared in the file
+    pub description: Option<String>,
+    /// Path to the .0 file
+    pub path: PathBuf,
+    /// Tunable parameters
+    pub params: Vec<ParamSpec>,
+}
+
+impl StrategyInfo {
+    /// Read metadata from a .0 graph file
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let builtin = builtin_dir().ok();
+        Self::load(path, path.parent().is_some_and(|dir| same_dir(dir, builtin.as_deref())))
+    }
+
+    /// Read metadata from a .0 graph file, with the bundled metadata if
+    /// `builtin` (the file is one of the bundled strategies)
+    fn load(path: &Path, builtin: bool) -> Result<Self, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| format!("Invalid strategy path: {}", path.display()))?
+            .to_string();
+
+        // Bundled metadata describes the bundled graphs only; a user graph
+        // that happens to share a name gets the generic schema
+        let metadata = if builtin {
+            builtin_params(&name)
+        } else {
+            Vec::new()
+        };
+        let params = config_constants(&source)
+            .map_err(|e| format!("{}: {}", path.display(), e))?
+            .into_iter()
+            .map(|(param, default)| match metadata.iter().find(|p| p.name == param) {
+                Some(meta) => ParamSpec::described(meta, default),
+                None => ParamSpec::generic(&param, default),
+            })
+            .collect();
+
+        Ok(Self {
+            name,
+            graph_name: read_field(&source, "name"),
+            description: read_field(&source, "description"),
+            path: path.to_path_buf(),
+            params,
+        })
+    }
+
+    /// Validate `name=value` overrides against the schema and return the
+    /// full parameter set with defaults filled in
+    pub fn resolve_params(&self, overrides: &[String]) -> Result<HashMap<String, f64>, String> {
+        let mut params: HashMap<String, f64> = self
+            .params
+            .iter()
+            .map(|p| (p.name.to_string(), p.default))
+            .collect();
+
+        for item in overrides {
+            let (name, value) = item
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid parameter '{}', expected name=value", item))?;
+
+            let spec = self.params.iter().find(|p| p.name == name.trim()).ok_or_else(|| {
+                let known: Vec<&str> = self.params.iter().map(|p| p.name.as_str()).collect();
+                format!(
+                    "Unknown parameter for {}: {}. Known: {:?}",
+                    self.name, name, known
+                )
+            })?;
+
+            params.insert(spec.name.clone(), spec.parse(value.trim())?);
+        }
+
+        Ok(params)
+    }
+}
+
+/// Strategies available by name
+pub struct StrategyRegistry {
+    strategies: Vec<StrategyInfo>,
+}
+
+impl StrategyRegistry {
+    /// Build a registry from the .0 graphs in a directory
+    pub fn discover(dir: &Path) -> Result<Self, String> {
+        Ok(Self {
+            strategies: discover(dir)?,
+        })
+    }
+
+    /// Build a registry from the bundled strategy directory
+    pub fn builtin() -> Result<Self, String> {
+        Self::discover(&builtin_dir()?)
+    }
+
+    /// All registered strategies, sorted by name
+    pub fn list(&self) -> &[StrategyInfo] {
+        &self.strategies
+    }
+
+    /// Look up a strategy by name
+    pub fn get(&self, name: &str) -> Option<&StrategyInfo> {
+        self.strategies.iter().find(|s| s.name == name)
+    }
+
+    /// Look up a strategy by name, listing the available ones if it's unknown
+    pub fn resolve(&self, name: &str) -> Result<StrategyInfo, String> {
+        if let Some(strategy) = self.get(name) {
+            return Ok(strategy.clone());
+        }
+
+        let available: Vec<&str> = self.strategies.iter().map(|s| s.name.as_str()).collect();
+        Err(format!("Unknown strategy: {}. Available: {:?}", name, available))
+    }
+}
+
+/// Discover all .0 strategy graphs in a directory, sorted by name
+pub fn discover(dir: &Path) -> Result<Vec<StrategyInfo>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read strategy directory {}: {}", dir.display(), e))?;
+
+    let builtin = same_dir(dir, builtin_dir().ok().as_deref());
+
+    let mut strategies = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("0") {
+            strategies.push(StrategyInfo::load(&path, builtin)?);
+        }
+    }
+
+    strategies.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(strategies)
+}
+
+/// Whether two paths name the same existing directory (e.g. the bundled
+/// strategy directory)
+fn same_dir(dir: &Path, other: Option<&Path>) -> bool {
+    let dir = dir.canonicalize().ok();
+    let other = other.and_then(|other| other.canonicalize().ok());
+    dir.is_some() && dir == other
+}
+
+/// Scalar `config_*` constants declared in a graph file, in declaration order
+fn config_constants(source: &str) -> Result<Vec<(String, f64)>, String> {
+    let mut constants: Vec<(String, f64)> = Vec::new();
+
+    for graph in graph::parse(source)? {
+        for node in graph.nodes.iter().filter(|n| n.node_type == NodeType::Constant) {
+            let (Some(name), Some(value)) = (node.id.strip_prefix(PARAM_PREFIX), &node.value) else {
+                continue;
+            };
+            if value.data.len() == 1 && !constants.iter().any(|(known, _)| known == name) {
+                // Widen through the literal so 0.0025 stays 0.0025
+                let default = value.data[0].to_string().parse().map_err(|e| format!("{}", e))?;
+                constants.push((name.to_string(), default));
+            }
+        }
+    }
+
+    Ok(constants)
+}
+
+/// Read the first `field: "value"` declaration from a graph's debug representation
+fn read_field(source: &str, field: &str) -> Option<String> {
+    let prefix = format!("{}:", field);
+
+    source.lines().find_map(|line| {
+        let line = line.trim_start_matches('#').trim();
+        let value = line.strip_prefix(&prefix)?.trim().trim_end_matches(',');
+        let value = value.strip_prefix('"')?.strip_suffix('"')?;
+        Some(value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_field() {
+        let source = "# Graph {\n#     name: \"market_making_v1\",\n#     description: \"Simple MM\",\n";
+
+        assert_eq!(read_field(source, "name"), Some("market_making_v1".to_string()));
+        assert_eq!(read_field(source, "description"), Some("Simple MM".to_string()));
+        assert_eq!(read_field(source, "version"), None);
+    }
+
+    #[test]
+    fn test_discover_bundled_strategies() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(STRATEGY_DIR);
+        let strategies = discover(&dir).unwrap();
+
+        let names: Vec<&str> = strategies.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["arbitrage", "grid_trading", "market_making"]);
+        assert_eq!(strategies[2].graph_name.as_deref(), Some("market_making_v1"));
+    }
+
+    #[test]
+    fn test_registry_resolve() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(STRATEGY_DIR);
+        let registry = StrategyRegistry::discover(&dir).unwrap();
+
+        let strategy = registry.resolve("grid_trading").unwrap();
+        assert_eq!(strategy.path, dir.join("grid_trading.0"));
+        assert!(registry.resolve("no_such_strategy").is_err());
+
+        let by_path = resolve(strategy.path.to_str().unwrap()).unwrap();
+        assert_eq!(by_path.name, "grid_trading");
+    }
+
+    #[test]
+    fn test_find_builtin_dir() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let bundled = root.join(STRATEGY_DIR);
+        let exe = root.join("target/debug/0-hummingbot");
+        let elsewhere = std::env::temp_dir();
+
+        // The root override wins over the executable-relative search
+        assert_eq!(find_builtin_dir(Some(root), Some(&exe), None).unwrap(), bundled);
+        assert!(find_builtin_dir(Some(&elsewhere), Some(&exe), None).is_err());
+
+        assert_eq!(find_builtin_dir(None, Some(&exe), None).unwrap(), bundled);
+        assert_eq!(find_builtin_dir(None, Some(&elsewhere.join("bin")), Some(root)).unwrap(), bundled);
+        assert!(find_builtin_dir(None, Some(&elsewhere.join("bin")), Some(&elsewhere)).is_err());
+    }
+
+    #[test]
+    fn test_builtin_metadata_matches_graph_constants() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(STRATEGY_DIR);
+
+        for strategy in discover(&dir).unwrap() {
+            let metadata = builtin_params(&strategy.name);
+            assert!(!metadata.is_empty(), "{} has no metadata", strategy.name);
+            assert_eq!(strategy.params.len(), metadata.len(), "{}", strategy.name);
+
+            for (param, meta) in strategy.params.iter().zip(&metadata) {
+                assert_eq!(param.name, meta.name, "{}", strategy.name);
+                assert_eq!(param.description, meta.description);
+                // The graph's default must be a valid value for the parameter
+                let default = param.default.to_string();
+                assert!(param.parse(&default).is_ok(), "{}.{}", strategy.name, param.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_user_graph_schema_comes_from_file() {
+        let dir = std::env::temp_dir().join(format!("zero-hummingbot-strategies-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // A user copy of a bundled strategy doesn't inherit its metadata
+        let bundled = Path::new(env!("CARGO_MANIFEST_DIR")).join(STRATEGY_DIR).join("market_making.0");
+        let copy = dir.join("market_making.0");
+        fs::copy(&bundled, &copy).unwrap();
+        let strategy = StrategyInfo::from_file(&copy).unwrap();
+        let names: Vec<&str> = strategy.params.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["spread", "order_size", "confidence_threshold", "max_position"]);
+        assert_eq!(strategy.resolve_params(&["spread=2".to_string()]).unwrap()["spread"], 2.0);
+
+        let custom = dir.join("custom.0");
+        let source = "# Graph {\n#     nodes: [\n#         {\n#             id: sha256(\"config_lookback\"),\n#             type: Constant,\n#             value: Tensor { shape: [], data: [20.0], confidence: 1.0 },\n#         },\n#     ],\n#     outputs: [sha256(\"config_lookback\")],\n# }\n";
+        fs::write(&custom, source).unwrap();
+        let strategy = StrategyInfo::from_file(&custom).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(strategy.params.len(), 1);
+        assert_eq!(strategy.params[0].name, "lookback");
+        assert_eq!(strategy.params[0].default, 20.0);
+        assert!(strategy.resolve_params(&["lookback=x".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_resolve_params() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(STRATEGY_DIR);
+        let registry = StrategyRegistry::discover(&dir).unwrap();
+        let grid = registry.get("grid_trading").unwrap();
+
+        let params = grid.resolve_params(&["num_levels=8".to_string()]).unwrap();
+        assert_eq!(params["num_levels"], 8.0);
+        assert_eq!(params["grid_spacing"], 0.01);
+
+        assert!(grid.resolve_params(&["num_levels=2.5".to_string()]).is_err());
+        assert!(grid.resolve_params(&["grid_spacing=2".to_string()]).is_err());
+        assert!(grid.resolve_params(&["spread=0.1".to_string()]).is_err());
+        assert!(grid.resolve_params(&["num_levels".to_string()]).is_err());
+    }
+}